@@ -0,0 +1,185 @@
+//! Incremental construction of validated agent metadata.
+
+use soroban_sdk::{ Bytes, Env, Vec };
+
+use crate::{ AgentMetadata, MetadataError, MetadataValidator };
+
+/// Collects metadata fields one at a time and validates them on `build`
+///
+/// Setters take `&mut self` so a single builder can be reused across
+/// submissions; see `clear`.
+#[derive(Clone, Debug, Default)]
+pub struct MetadataBuilder {
+    json_cid: Option<Bytes>,
+    model_hash: Option<Bytes>,
+    name: Option<Bytes>,
+    description: Option<Bytes>,
+    version: Option<Bytes>,
+    extra_fields: Option<Vec<(Bytes, Bytes)>>,
+}
+
+impl MetadataBuilder {
+    /// Create a builder with every field unset
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a builder pre-populated with every field of `metadata`
+    ///
+    /// Starting point for a new version: override only what changed.
+    pub fn from_existing(metadata: &AgentMetadata) -> Self {
+        Self {
+            json_cid: Some(metadata.json_cid.clone()),
+            model_hash: Some(metadata.model_hash.clone()),
+            name: Some(metadata.name.clone()),
+            description: Some(metadata.description.clone()),
+            version: Some(metadata.version.clone()),
+            extra_fields: Some(metadata.extra_fields.clone()),
+        }
+    }
+
+    pub fn json_cid(&mut self, json_cid: Bytes) -> &mut Self {
+        self.json_cid = Some(json_cid);
+        self
+    }
+
+    pub fn model_hash(&mut self, model_hash: Bytes) -> &mut Self {
+        self.model_hash = Some(model_hash);
+        self
+    }
+
+    pub fn name(&mut self, name: Bytes) -> &mut Self {
+        self.name = Some(name);
+        self
+    }
+
+    pub fn description(&mut self, description: Bytes) -> &mut Self {
+        self.description = Some(description);
+        self
+    }
+
+    pub fn version(&mut self, version: Bytes) -> &mut Self {
+        self.version = Some(version);
+        self
+    }
+
+    /// Replace all extra fields
+    pub fn extra_fields(&mut self, extra_fields: Vec<(Bytes, Bytes)>) -> &mut Self {
+        self.extra_fields = Some(extra_fields);
+        self
+    }
+
+    /// Append a single extra field
+    pub fn extra_field(&mut self, env: &Env, key: Bytes, value: Bytes) -> &mut Self {
+        self.extra_fields
+            .get_or_insert_with(|| Vec::new(env))
+            .push_back((key, value));
+        self
+    }
+
+    /// Reset every field to unset so the builder can be reused
+    pub fn clear(&mut self) -> &mut Self {
+        *self = Self::new();
+        self
+    }
+
+    /// Validate the collected fields with `validator`
+    ///
+    /// An unset scalar field fails with `MissingRequiredField`; unset extra
+    /// fields default to empty.
+    pub fn build(
+        &self,
+        env: &Env,
+        validator: &MetadataValidator
+    ) -> Result<AgentMetadata, MetadataError> {
+        let required = |field: &Option<Bytes>| {
+            field.clone().ok_or(MetadataError::MissingRequiredField)
+        };
+
+        validator.validate_and_parse(
+            env,
+            required(&self.json_cid)?,
+            required(&self.model_hash)?,
+            required(&self.name)?,
+            required(&self.description)?,
+            required(&self.version)?,
+            self.extra_fields.clone().unwrap_or_else(|| Vec::new(env))
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clear_allows_reuse() {
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+        let mut builder = MetadataBuilder::new();
+
+        let first = builder
+            .json_cid(Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"))
+            .model_hash(Bytes::from_slice(&env, b"a1b2c3d4e5f6789012345678901234567890abcdef"))
+            .name(Bytes::from_slice(&env, b"FirstAgent"))
+            .description(Bytes::from_slice(&env, b"The first agent"))
+            .version(Bytes::from_slice(&env, b"1.0.0"))
+            .extra_field(
+                &env,
+                Bytes::from_slice(&env, b"license"),
+                Bytes::from_slice(&env, b"MIT")
+            )
+            .build(&env, &validator)
+            .unwrap();
+        assert_eq!(first.extra_fields.len(), 1);
+
+        // A cleared builder fails exactly like a fresh one
+        builder.clear();
+        assert_eq!(
+            builder.build(&env, &validator),
+            MetadataBuilder::new().build(&env, &validator)
+        );
+        assert_eq!(builder.build(&env, &validator), Err(MetadataError::MissingRequiredField));
+
+        let second = builder
+            .json_cid(
+                Bytes::from_slice(&env, b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi")
+            )
+            .model_hash(Bytes::from_slice(&env, b"00000000000000000000000000000000"))
+            .name(Bytes::from_slice(&env, b"SecondAgent"))
+            .description(Bytes::from_slice(&env, b"The second agent"))
+            .version(Bytes::from_slice(&env, b"2.0.0"))
+            .build(&env, &validator)
+            .unwrap();
+
+        assert_eq!(second.name, Bytes::from_slice(&env, b"SecondAgent"));
+        assert_eq!(second.extra_fields.len(), 0);
+    }
+
+    #[test]
+    fn test_from_existing_carries_fields_over() {
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+        let existing = MetadataBuilder::new()
+            .json_cid(Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"))
+            .model_hash(Bytes::from_slice(&env, b"a1b2c3d4e5f6789012345678901234567890abcdef"))
+            .name(Bytes::from_slice(&env, b"TestAgent"))
+            .description(Bytes::from_slice(&env, b"A test agent"))
+            .version(Bytes::from_slice(&env, b"1.0.0"))
+            .extra_field(
+                &env,
+                Bytes::from_slice(&env, b"kind"),
+                Bytes::from_slice(&env, b"trader")
+            )
+            .build(&env, &validator)
+            .unwrap();
+
+        let updated = MetadataBuilder::from_existing(&existing)
+            .version(Bytes::from_slice(&env, b"1.1.0"))
+            .build(&env, &validator)
+            .unwrap();
+
+        assert_eq!(updated.version, Bytes::from_slice(&env, b"1.1.0"));
+        assert_eq!(AgentMetadata { version: existing.version.clone(), ..updated }, existing);
+    }
+}
@@ -0,0 +1,82 @@
+//! Small LRU of CID validation results for a single validator instance.
+
+use soroban_sdk::{ Bytes, Env };
+
+use crate::MetadataError;
+
+/// Number of CIDs remembered by `CidCache`
+pub const CID_CACHE_CAPACITY: usize = 4;
+
+type Entry = ([u8; 32], Result<(), MetadataError>);
+
+/// Memoized `validate_cid` results keyed by the SHA-256 of the CID bytes
+///
+/// Entries are kept most recently used first; inserting into a full cache
+/// drops the least recently used one.
+pub struct CidCache {
+    entries: [Option<Entry>; CID_CACHE_CAPACITY],
+    hits: u32,
+}
+
+impl CidCache {
+    pub fn new() -> Self {
+        Self { entries: [None; CID_CACHE_CAPACITY], hits: 0 }
+    }
+
+    /// Cache key for `cid`
+    pub fn key(env: &Env, cid: &Bytes) -> [u8; 32] {
+        env.crypto().sha256(cid).to_array()
+    }
+
+    /// Cached result for `key`, promoting it to most recently used
+    pub fn get(&mut self, key: &[u8; 32]) -> Option<Result<(), MetadataError>> {
+        let index = self.entries
+            .iter()
+            .position(|entry| matches!(entry, Some((k, _)) if k == key))?;
+
+        let entry = self.entries[index];
+        self.entries.copy_within(0..index, 1);
+        self.entries[0] = entry;
+        self.hits = self.hits.saturating_add(1);
+        entry.map(|(_, result)| result)
+    }
+
+    /// Record `result` for `key` as most recently used
+    pub fn insert(&mut self, key: [u8; 32], result: Result<(), MetadataError>) {
+        self.entries.copy_within(0..CID_CACHE_CAPACITY - 1, 1);
+        self.entries[0] = Some((key, result));
+    }
+
+    /// Number of lookups answered from the cache
+    pub fn hits(&self) -> u32 {
+        self.hits
+    }
+}
+
+impl Default for CidCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_least_recently_used_entry_evicted() {
+        let mut cache = CidCache::new();
+        for i in 0..CID_CACHE_CAPACITY as u8 {
+            cache.insert([i; 32], Ok(()));
+        }
+
+        // Touch the oldest entry so the next oldest is evicted instead
+        assert_eq!(cache.get(&[0; 32]), Some(Ok(())));
+        cache.insert([9; 32], Err(MetadataError::InvalidCidFormat));
+
+        assert_eq!(cache.get(&[1; 32]), None);
+        assert_eq!(cache.get(&[0; 32]), Some(Ok(())));
+        assert_eq!(cache.get(&[9; 32]), Some(Err(MetadataError::InvalidCidFormat)));
+        assert_eq!(cache.hits(), 3);
+    }
+}
@@ -0,0 +1,584 @@
+//! Structural helpers for content identifiers (CIDs).
+//!
+//! These complement the length checks in the common-utils `CIDValidator` with
+//! knowledge of CID versions and multibase prefixes.
+
+use common_utils::validator::{ CID_MAX_LENGTH, CID_MIN_LENGTH };
+use soroban_sdk::{ contracttype, Bytes, Env, Vec };
+
+use crate::{ config::CasePolicy, hash, MetadataError };
+
+/// Lowercase RFC 4648 base32 alphabet used by multibase `b`
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+
+/// Multicodec code for dag-pb, the implicit codec of every CIDv0
+pub const DAG_PB: u64 = 0x70;
+
+/// Multicodec code for raw binary
+pub const RAW: u64 = 0x55;
+
+/// Multicodec code for dag-json
+pub const DAG_JSON: u64 = 0x0129;
+
+/// Multihash prefix of a CIDv0: sha2-256 (`0x12`) with a 32-byte digest
+const CIDV0_MULTIHASH_PREFIX: [u8; 2] = [0x12, 0x20];
+
+/// Length of a base58btc CIDv0 string (`Qm` + 44 characters)
+pub const CIDV0_LENGTH: u32 = 46;
+
+/// Shortest CID accepted by the default `CIDValidator`
+pub const MIN_LENGTH: usize = CID_MIN_LENGTH;
+
+/// Longest CID accepted by the default `CIDValidator`
+pub const MAX_LENGTH: usize = CID_MAX_LENGTH;
+
+/// Decoded components of a CID, for display
+///
+/// Contract types have no `u8` or `u16`, so the fields are `u32`; `decompose`
+/// keeps `version` within `u8` and `codec` and `hash_code` within `u16`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct CidParts {
+    /// CID version (0 or 1)
+    pub version: u32,
+    /// Multicodec content type
+    pub codec: u32,
+    /// Multihash function code
+    pub hash_code: u32,
+    /// Length of the multihash digest in bytes
+    pub digest_len: u32,
+}
+
+/// Whether a CID's first byte marks a pasted JSON document (`{` or `[`)
+pub fn is_json_blob_prefix(first: Option<u8>) -> bool {
+    matches!(first, Some(b'{') | Some(b'['))
+}
+
+/// Env-free check of a CID against the default validator rules
+///
+/// Mirrors `MetadataValidator::new().validate_cid`: the default length
+/// bounds and the default `LowerOnly` case policy for base32 CIDv1, checked
+/// by the same rule as `matches_case_policy`. Useful for offline tooling that
+/// has raw bytes but no Soroban `Env`.
+pub fn is_valid_cid(cid: &[u8]) -> bool {
+    (MIN_LENGTH..=MAX_LENGTH).contains(&cid.len()) &&
+        case_policy_allows(
+            cid.first().copied(),
+            cid.iter().copied().skip(1),
+            CasePolicy::LowerOnly
+        )
+}
+
+/// Env-free CID validation returning the crate error code
+pub fn validate(cid: &[u8]) -> Result<(), MetadataError> {
+    if is_valid_cid(cid) {
+        Ok(())
+    } else {
+        Err(MetadataError::InvalidCidFormat)
+    }
+}
+
+/// Whether `b` is in the lowercase RFC 4648 base32 alphabet (`a-z`, `2-7`)
+pub fn is_base32_lower(b: u8) -> bool {
+    matches!(b, b'a'..=b'z' | b'2'..=b'7')
+}
+
+/// Fast-path check for a lowercase base32 CIDv1 string
+///
+/// Checks the `b` multibase prefix, the default length window and the base32
+/// alphabet over the remainder in a single allocation-free pass.
+pub fn is_valid_cidv1_base32(cid: &Bytes) -> bool {
+    let len = cid.len() as usize;
+    if !(MIN_LENGTH..=MAX_LENGTH).contains(&len) || cid.first() != Some(b'b') {
+        return false;
+    }
+
+    cid.iter().skip(1).all(is_base32_lower)
+}
+
+/// Whether a base32 CIDv1 string satisfies `policy`
+///
+/// The multibase prefix decides the case (`b` lower, `B` upper) and every
+/// body character must be in that case's alphabet. Strings that are not
+/// base32 CIDv1 (no `b`/`B` prefix) are outside the policy and pass.
+pub fn matches_case_policy(cid: &Bytes, policy: CasePolicy) -> bool {
+    case_policy_allows(cid.first(), cid.iter().skip(1), policy)
+}
+
+/// Case policy rule shared by the host (`Bytes`) and env-free (`&[u8]`) paths
+fn case_policy_allows(
+    first: Option<u8>,
+    mut body: impl Iterator<Item = u8>,
+    policy: CasePolicy
+) -> bool {
+    let upper = match first {
+        Some(b'b') => false,
+        Some(b'B') => true,
+        _ => {
+            return true;
+        }
+    };
+
+    let allowed = match policy {
+        CasePolicy::LowerOnly => !upper,
+        CasePolicy::UpperOnly => upper,
+        CasePolicy::Either => true,
+    };
+
+    allowed &&
+        body.all(|c| {
+            if upper {
+                !c.is_ascii_lowercase() && is_base32_lower(c.to_ascii_lowercase())
+            } else {
+                is_base32_lower(c)
+            }
+        })
+}
+
+/// Encode bytes as unpadded lowercase base32
+pub fn base32_encode(env: &Env, data: &Bytes) -> Bytes {
+    let mut out = Bytes::new(env);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for b in data.iter() {
+        buffer = (buffer << 8) | (b as u32);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push_back(BASE32_ALPHABET[((buffer >> bits) & 0x1F) as usize]);
+        }
+        buffer &= (1 << bits) - 1;
+    }
+    if bits > 0 {
+        out.push_back(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1F) as usize]);
+    }
+
+    out
+}
+
+/// Decode unpadded base32 in either case
+///
+/// Returns `None` on any character outside the alphabet.
+pub fn base32_decode(env: &Env, text: &Bytes) -> Option<Bytes> {
+    let mut out = Bytes::new(env);
+    let mut buffer: u32 = 0;
+    let mut bits = 0;
+
+    for c in text.iter() {
+        let value = match c.to_ascii_lowercase() {
+            c @ b'a'..=b'z' => c - b'a',
+            c @ b'2'..=b'7' => c - b'2' + 26,
+            _ => {
+                return None;
+            }
+        };
+        buffer = (buffer << 5) | (value as u32);
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push_back((buffer >> bits) as u8);
+            buffer &= (1 << bits) - 1;
+        }
+    }
+
+    Some(out)
+}
+
+/// Decode a CID into its binary form
+///
+/// CIDv0 yields the bare 34-byte multihash; CIDv1 in base32 (`b`/`B`) or
+/// base58btc (`z`) yields the version-prefixed bytes. Returns `None` for
+/// other multibases or undecodable input.
+pub fn decode(env: &Env, cid: &Bytes) -> Option<Bytes> {
+    if is_cidv0_text(cid) {
+        return hash::decode_base58(env, cid);
+    }
+
+    let body = cid.slice(1..);
+    let binary = match cid.first()? {
+        b'b' | b'B' => base32_decode(env, &body)?,
+        b'z' => hash::decode_base58(env, &body)?,
+        _ => {
+            return None;
+        }
+    };
+    let mut offset = 0;
+    if read_varint(&binary, &mut offset)? != 1 {
+        return None;
+    }
+    Some(binary)
+}
+
+/// Convert a CID to its canonical CIDv1 lowercase base32 text form
+///
+/// A CIDv0 is upgraded by prefixing the version (`0x01`) and dag-pb codec
+/// (`0x70`) to its multihash. Returns `None` if the CID cannot be decoded.
+pub fn to_cidv1_base32(env: &Env, cid: &Bytes) -> Option<Bytes> {
+    let binary = if version(env, cid)? == 0 {
+        let multihash = decode(env, cid)?;
+        let prefix = Bytes::from_array(env, &CIDV0_MULTIHASH_PREFIX);
+        if multihash.len() != 34 || multihash.slice(0..2) != prefix {
+            return None;
+        }
+        let mut upgraded = Bytes::from_array(env, &[0x01, DAG_PB as u8]);
+        upgraded.append(&multihash);
+        upgraded
+    } else {
+        decode(env, cid)?
+    };
+
+    let mut out = Bytes::from_slice(env, b"b");
+    out.append(&base32_encode(env, &binary));
+    Some(out)
+}
+
+/// Whether `cid` starts with any of `prefixes`
+pub fn has_any_prefix(cid: &Bytes, prefixes: &Vec<Bytes>) -> bool {
+    prefixes
+        .iter()
+        .any(|prefix| prefix.len() <= cid.len() && cid.slice(0..prefix.len()) == prefix)
+}
+
+/// Length of `short_checksum` output in hex characters
+pub const SHORT_CHECKSUM_LENGTH: usize = 6;
+
+/// Short checksum for visual comparison of CIDs
+///
+/// The first six lowercase hex characters of `sha256(cid)`, computed over the
+/// CID text as given. Not collision resistant; for display only.
+pub fn short_checksum(env: &Env, cid: &Bytes) -> Bytes {
+    let digest = env.crypto().sha256(cid).to_array();
+    hash::encode_hex(env, &digest[..SHORT_CHECKSUM_LENGTH / 2])
+}
+
+/// Multicodec content type of a CID
+///
+/// A CIDv0 is always dag-pb. For a CIDv1 the codec varint following the
+/// version is returned. Returns `None` for input that cannot be decoded.
+pub fn codec(env: &Env, cid: &Bytes) -> Option<u64> {
+    if version(env, cid)? == 0 {
+        return Some(DAG_PB);
+    }
+
+    let binary = decode(env, cid)?;
+    let mut offset = 0;
+    read_varint(&binary, &mut offset)?;
+    read_varint(&binary, &mut offset)
+}
+
+/// Split a CID into version, codec, hash function and digest length
+///
+/// A CIDv0 reports version 0 and the implicit dag-pb codec. Returns `None`
+/// for input that cannot be decoded, a version wider than 8 bits, a codec or
+/// hash code wider than 16 bits, or a digest whose length disagrees with its
+/// declared length.
+pub fn decompose(env: &Env, cid: &Bytes) -> Option<CidParts> {
+    let (version, codec, hash_code, digest) = split(env, cid)?;
+    Some(CidParts {
+        version: u8::try_from(version).ok()?.into(),
+        codec: u16::try_from(codec).ok()?.into(),
+        hash_code: u16::try_from(hash_code).ok()?.into(),
+        digest_len: digest.len(),
+    })
+}
+
+/// Raw multihash digest embedded in a CID
+///
+/// Returns `None` under the same conditions as `decompose`.
+pub fn digest(env: &Env, cid: &Bytes) -> Option<Bytes> {
+    split(env, cid).map(|(_, _, _, digest)| digest)
+}
+
+/// Decode a CID into (version, codec, hash code, digest)
+fn split(env: &Env, cid: &Bytes) -> Option<(u64, u64, u64, Bytes)> {
+    let binary = decode(env, cid)?;
+    let mut offset = 0;
+    let (version, codec) = if version(env, cid)? == 0 {
+        (0, DAG_PB)
+    } else {
+        let version = read_varint(&binary, &mut offset)?;
+        (version, read_varint(&binary, &mut offset)?)
+    };
+    let hash_code = read_varint(&binary, &mut offset)?;
+    let digest_len = read_varint(&binary, &mut offset)?;
+    if u64::from(binary.len() - offset) != digest_len {
+        return None;
+    }
+
+    Some((version, codec, hash_code, binary.slice(offset..)))
+}
+
+/// Whether a CIDv1 points to dag-json content
+pub fn is_dag_json(env: &Env, cid: &Bytes) -> bool {
+    codec(env, cid) == Some(DAG_JSON)
+}
+
+/// Multihash function code embedded in a CIDv1
+///
+/// Decodes the CID, skips the version and codec varints and reads the
+/// multihash function-code varint (`0x12` for sha2-256, `0xb220` for
+/// blake2b-256). Returns `None` for a CIDv0, whose hash is implicitly
+/// sha2-256, and for input that cannot be decoded or whose code exceeds 16 bits.
+pub fn hash_function_code(env: &Env, cid: &Bytes) -> Option<u16> {
+    if version(env, cid)? == 0 {
+        return None;
+    }
+
+    let binary = decode(env, cid)?;
+    let mut offset = 0;
+    read_varint(&binary, &mut offset)?;
+    read_varint(&binary, &mut offset)?;
+    u16::try_from(read_varint(&binary, &mut offset)?).ok()
+}
+
+/// Read an unsigned LEB128 varint at `offset`, advancing past it
+///
+/// Returns `None` on truncated input or a value longer than 9 bytes.
+fn read_varint(data: &Bytes, offset: &mut u32) -> Option<u64> {
+    let mut value = 0u64;
+    for shift in (0..63).step_by(7) {
+        let byte = data.get(*offset)?;
+        *offset += 1;
+        value |= u64::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Some(value);
+        }
+    }
+    None
+}
+
+/// Detect the CID version by decoding it
+///
+/// A CIDv0 is a 46-character base58btc string starting with `Qm` that
+/// decodes to a sha2-256 multihash. A CIDv1 must decode (base32 `b`/`B` or
+/// base58btc `z`) and its leading varint must read `1`. Returns `None`
+/// otherwise, including for other multibases.
+pub fn version(env: &Env, cid: &Bytes) -> Option<u32> {
+    if is_cidv0_text(cid) {
+        let multihash = hash::decode_base58(env, cid)?;
+        let prefix = Bytes::from_array(env, &CIDV0_MULTIHASH_PREFIX);
+        if multihash.len() != 34 || multihash.slice(0..2) != prefix {
+            return None;
+        }
+        return Some(0);
+    }
+
+    let binary = decode(env, cid)?;
+    let mut offset = 0;
+    u32::try_from(read_varint(&binary, &mut offset)?).ok()
+}
+
+/// Whether `cid` has the textual shape of a CIDv0 (`Qm` + 44 characters)
+fn is_cidv0_text(cid: &Bytes) -> bool {
+    cid.len() == CIDV0_LENGTH && cid.get(0) == Some(b'Q') && cid.get(1) == Some(b'm')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::Env;
+
+    #[test]
+    fn test_version_detection() {
+        let env = Env::default();
+
+        let v0 = Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
+        let v1 = Bytes::from_slice(
+            &env,
+            b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
+        );
+        let unknown = Bytes::from_slice(&env, b"not-a-cid");
+
+        let bogus_v1 = Bytes::from_slice(&env, b"bogus-string-xx");
+        let bogus_v0 = Bytes::from_slice(&env, b"Qm00000000000000000000000000000000000000000000");
+
+        assert_eq!(version(&env, &v0), Some(0));
+        assert_eq!(version(&env, &v1), Some(1));
+        assert_eq!(version(&env, &unknown), None);
+        assert_eq!(version(&env, &bogus_v1), None);
+        assert_eq!(version(&env, &bogus_v0), None);
+    }
+
+    #[test]
+    fn test_env_free_validate() {
+        assert!(validate(b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG").is_ok());
+        assert_eq!(validate(b"Qm"), Err(MetadataError::InvalidCidFormat));
+        assert_eq!(
+            validate(b"BAFYBEIGDYRZT5SFP7UDM7HU76UH7Y26NF3EFUYLQABF3OCLGTQY55FBZDI"),
+            Err(MetadataError::InvalidCidFormat)
+        );
+        assert_eq!(
+            validate(b"bafyBEIGDYRZT5SFP7UDM7HU76UH7Y26NF3EFUYLQABF3OCLGTQY55FBZDI"),
+            Err(MetadataError::InvalidCidFormat)
+        );
+        assert_eq!(validate(&[b'Q'; 101]), Err(MetadataError::InvalidCidFormat));
+    }
+
+    #[test]
+    fn test_is_valid_cidv1_base32() {
+        let env = Env::default();
+
+        let valid = Bytes::from_slice(
+            &env,
+            b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
+        );
+        let wrong_prefix = Bytes::from_slice(
+            &env,
+            b"zafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
+        );
+        let bad_char = Bytes::from_slice(
+            &env,
+            b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzd1"
+        );
+        let v0 = Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
+
+        assert!(is_valid_cidv1_base32(&valid));
+        assert!(!is_valid_cidv1_base32(&wrong_prefix));
+        assert!(!is_valid_cidv1_base32(&bad_char));
+        assert!(!is_valid_cidv1_base32(&v0));
+        assert!(!is_valid_cidv1_base32(&Bytes::from_slice(&env, b"b")));
+    }
+
+    #[test]
+    fn test_base32_round_trip() {
+        let env = Env::default();
+        let data = Bytes::from_slice(&env, b"foobar");
+
+        let encoded = base32_encode(&env, &data);
+        assert_eq!(encoded, Bytes::from_slice(&env, b"mzxw6ytboi"));
+        assert_eq!(base32_decode(&env, &encoded), Some(data));
+        assert_eq!(base32_decode(&env, &Bytes::from_slice(&env, b"mzxw1")), None);
+    }
+
+    #[test]
+    fn test_to_cidv1_base32() {
+        let env = Env::default();
+
+        let v0 = Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
+        let expected = Bytes::from_slice(
+            &env,
+            b"bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho34"
+        );
+        assert_eq!(to_cidv1_base32(&env, &v0), Some(expected.clone()));
+
+        // Already canonical input round-trips; uppercase is folded
+        assert_eq!(to_cidv1_base32(&env, &expected), Some(expected.clone()));
+        let upper = Bytes::from_slice(
+            &env,
+            b"BAFYBEIE5NQV6KD3QNFJUPGVZ34WOH3OKSC3IAU6ABMYAJN7QVTF6D2HO34"
+        );
+        assert_eq!(to_cidv1_base32(&env, &upper), Some(expected));
+
+        assert_eq!(to_cidv1_base32(&env, &Bytes::from_slice(&env, b"not-a-cid")), None);
+    }
+
+    #[test]
+    fn test_matches_case_policy() {
+        let env = Env::default();
+
+        let lower = Bytes::from_slice(
+            &env,
+            b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
+        );
+        let upper = Bytes::from_slice(
+            &env,
+            b"BAFYBEIGDYRZT5SFP7UDM7HU76UH7Y26NF3EFUYLQABF3OCLGTQY55FBZDI"
+        );
+        let mixed = Bytes::from_slice(
+            &env,
+            b"BAFYBEIGDYRZT5SFP7UDM7HU76UH7Y26NF3EFUYLQABF3OCLGTQY55fbzdi"
+        );
+
+        assert!(matches_case_policy(&lower, CasePolicy::LowerOnly));
+        assert!(!matches_case_policy(&upper, CasePolicy::LowerOnly));
+        assert!(matches_case_policy(&upper, CasePolicy::UpperOnly));
+        assert!(!matches_case_policy(&lower, CasePolicy::UpperOnly));
+        assert!(matches_case_policy(&lower, CasePolicy::Either));
+        assert!(matches_case_policy(&upper, CasePolicy::Either));
+        assert!(!matches_case_policy(&mixed, CasePolicy::Either));
+    }
+
+    #[test]
+    fn test_hash_function_code() {
+        let env = Env::default();
+
+        let sha2 = Bytes::from_slice(
+            &env,
+            b"bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho34"
+        );
+        assert_eq!(hash_function_code(&env, &sha2), Some(0x12));
+
+        // raw codec, blake2b-256 (varint 0xa0 0xe4 0x02), 32-byte digest
+        let mut binary = Bytes::from_array(&env, &[0x01, 0x55, 0xa0, 0xe4, 0x02, 0x20]);
+        binary.extend_from_slice(&[0xab; 32]);
+        let mut blake2b = Bytes::from_slice(&env, b"b");
+        blake2b.append(&base32_encode(&env, &binary));
+        assert_eq!(hash_function_code(&env, &blake2b), Some(0xb220));
+
+        let v0 = Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
+        assert_eq!(hash_function_code(&env, &v0), None);
+        assert_eq!(hash_function_code(&env, &Bytes::from_slice(&env, b"bafy")), None);
+    }
+
+    #[test]
+    fn test_short_checksum() {
+        let env = Env::default();
+        let cid = Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
+
+        let other = Bytes::from_slice(
+            &env,
+            b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
+        );
+
+        assert_eq!(short_checksum(&env, &cid), Bytes::from_slice(&env, b"293fa9"));
+        assert_ne!(short_checksum(&env, &other), short_checksum(&env, &cid));
+    }
+
+    #[test]
+    fn test_is_dag_json() {
+        let env = Env::default();
+
+        // dag-json codec (varint 0xa9 0x02), sha2-256, 32-byte digest
+        let mut binary = Bytes::from_array(&env, &[0x01, 0xa9, 0x02, 0x12, 0x20]);
+        binary.extend_from_slice(&[0xcd; 32]);
+        let mut dag_json = Bytes::from_slice(&env, b"b");
+        dag_json.append(&base32_encode(&env, &binary));
+
+        let dag_pb = Bytes::from_slice(
+            &env,
+            b"bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho34"
+        );
+        let v0 = Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
+
+        assert_eq!(codec(&env, &dag_json), Some(DAG_JSON));
+        assert!(is_dag_json(&env, &dag_json));
+        assert_eq!(codec(&env, &dag_pb), Some(DAG_PB));
+        assert!(!is_dag_json(&env, &dag_pb));
+        assert!(!is_dag_json(&env, &v0));
+    }
+
+    #[test]
+    fn test_decompose() {
+        let env = Env::default();
+        let expected = CidParts { version: 1, codec: 0x70, hash_code: 0x12, digest_len: 32 };
+
+        let v1 = Bytes::from_slice(
+            &env,
+            b"bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho34"
+        );
+        assert_eq!(decompose(&env, &v1), Some(expected.clone()));
+
+        let v0 = Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
+        assert_eq!(decompose(&env, &v0), Some(CidParts { version: 0, ..expected }));
+
+        assert_eq!(decompose(&env, &Bytes::from_slice(&env, b"not-a-cid")), None);
+
+        // Codec 0x10000 does not fit the 16-bit field
+        let mut binary = Bytes::from_array(&env, &[0x01, 0x80, 0x80, 0x04, 0x12, 0x20]);
+        binary.extend_from_slice(&[0x42; 32]);
+        let mut wide = Bytes::from_slice(&env, b"b");
+        wide.append(&base32_encode(&env, &binary));
+        assert_eq!(decompose(&env, &wide), None);
+    }
+}
@@ -0,0 +1,876 @@
+//! Parser configuration for agent metadata validation.
+//!
+//! `ParserConfig` layers metadata-specific policy on top of the per-field
+//! `ValidatorConfig` bounds used by the common-utils validators.
+
+use soroban_sdk::{ Bytes, Env, Vec };
+
+use crate::{ cid, MetadataError };
+
+/// Version byte leading an encoded limit profile
+pub const PROFILE_VERSION: u8 = 2;
+
+/// Size of an encoded limit profile in bytes
+pub const PROFILE_LENGTH: u32 = 83;
+
+const FLAG_REJECT_JSON_BLOB_CID: u64 = 1 << 0;
+const FLAG_REJECT_BLANK_TEXT: u64 = 1 << 1;
+const FLAG_VALIDATE_CREATED_AT: u64 = 1 << 2;
+const FLAG_REQUIRE_PRINTABLE_TEXT: u64 = 1 << 3;
+const FLAG_REQUIRE_UTF8_DESCRIPTION: u64 = 1 << 4;
+const FLAG_FORBID_RESERVED_KEYS: u64 = 1 << 5;
+const FLAG_ACCEPT_BASE58_HASH: u64 = 1 << 6;
+const FLAG_ALLOW_EMPTY_DESCRIPTION: u64 = 1 << 7;
+const FLAG_VALIDATE_ASSET_CIDS: u64 = 1 << 8;
+const FLAG_DEEP_SCAN: u64 = 1 << 9;
+const FLAG_VALIDATE_SIG_CID: u64 = 1 << 10;
+const FLAG_NAME_MUST_BE_DNS_LABEL: u64 = 1 << 11;
+const FLAG_REJECT_DUPLICATE_TAGS: u64 = 1 << 12;
+const FLAG_FORBID_PRERELEASE: u64 = 1 << 13;
+const FLAG_REQUIRE_SORTED_EXTRA: u64 = 1 << 14;
+const FLAG_VALIDATE_MODEL_SIZE: u64 = 1 << 15;
+const FLAG_REQUIRE_DISTINCT_NAME_DESCRIPTION: u64 = 1 << 16;
+const FLAG_LOWERCASE_HASH: u64 = 1 << 17;
+const FLAG_STRICT_DEPRECATED: u64 = 1 << 18;
+const FLAG_REQUIRE_EXTRA_FIELDS: u64 = 1 << 19;
+const FLAG_FORBID_REDUNDANT_ASSET_CID: u64 = 1 << 20;
+const FLAG_VALIDATE_RANK: u64 = 1 << 21;
+const FLAG_REJECT_LEADING_ZEROS: u64 = 1 << 22;
+const FLAG_REQUIRE_CIDV1_BASE32: u64 = 1 << 23;
+const FLAG_FORBID_EMPTY_EXTRA_VALUES: u64 = 1 << 24;
+const FLAG_STORE_RAW_HASH: u64 = 1 << 25;
+const FLAG_FORBID_ZERO_VERSION: u64 = 1 << 26;
+const FLAG_FORBID_SCALAR_KEY_SHADOWING: u64 = 1 << 27;
+const FLAG_ACCEPT_BASE64_HASH: u64 = 1 << 28;
+const FLAG_TYPE_CHECK_WELL_KNOWN: u64 = 1 << 29;
+const FLAG_REQUIRE_SPDX_LICENSE: u64 = 1 << 30;
+const FLAG_FORBID_NUMERIC_NAME: u64 = 1 << 31;
+const FLAG_REQUIRE_ASSETS_WITH_MODEL_SIZE: u64 = 1 << 32;
+const FLAG_ASCII_ONLY_NAME: u64 = 1 << 33;
+const FLAG_VALIDATE_MAINTAINERS: u64 = 1 << 34;
+const KNOWN_FLAGS: u64 = (1 << 35) - 1;
+
+/// Which base32 case a CIDv1 may use
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CasePolicy {
+    /// Multibase `b` with lowercase body (multiformats canonical form)
+    LowerOnly,
+    /// Multibase `B` with uppercase body
+    UpperOnly,
+    /// Either form, as long as prefix and body agree
+    Either,
+}
+
+/// Versioning scheme the `version` field must follow
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum VersionScheme {
+    /// `MAJOR.MINOR.PATCH` with optional pre-release and build suffixes
+    SemVer,
+    /// `YYYY.MM.DD` calendar versioning
+    CalVer,
+    /// No scheme enforced beyond the version validator
+    Any,
+}
+
+/// Metadata-level validation policy
+#[derive(Clone, Debug)]
+pub struct ParserConfig {
+    /// Reject a `json_cid` that starts with `{` or `[` (a pasted JSON blob)
+    pub reject_json_blob_cid: bool,
+    /// Reject a name or description made up solely of whitespace bytes
+    pub reject_blank_text: bool,
+    /// Check a `created_at` extra field, when present, against the ledger time
+    pub validate_created_at: bool,
+    /// Seconds a `created_at` timestamp may run ahead of the ledger time
+    pub created_at_max_skew: u64,
+    /// Reject control bytes in the name, description and extra field values,
+    /// and restrict extra field keys to the safe key charset
+    pub require_printable_text: bool,
+    /// Require the description to be well-formed UTF-8
+    pub require_utf8_description: bool,
+    /// Reject extra field keys in the reserved `_` namespace
+    pub forbid_reserved_keys: bool,
+    /// Maximum number of extra fields
+    pub max_extra_fields: u32,
+    /// Maximum combined byte size of all extra field keys and values
+    pub max_extra_bytes: u32,
+    /// Maximum byte length of a single extra field key
+    pub max_extra_key_length: u32,
+    /// Minimum accepted CID version (0 accepts CIDv0 and CIDv1)
+    pub min_cid_version: u32,
+    /// When set, require a `content_type` extra field holding one of these values
+    pub allowed_content_types: Option<Vec<Bytes>>,
+    /// Accept base58-encoded model hashes that fail the hex hash validator
+    pub accept_base58_hash: bool,
+    /// When set, require a `kind` extra field holding one of these values
+    pub allowed_kinds: Option<Vec<Bytes>>,
+    /// Accept an empty description; the name stays required
+    pub allow_empty_description: bool,
+    /// Validate every CID listed in an `assets` extra field like `json_cid`
+    pub validate_asset_cids: bool,
+    /// Accepted case for base32 CIDv1 strings
+    pub cidv1_case: CasePolicy,
+    /// Inspect every byte of every field instead of relying on length checks
+    pub deep_scan: bool,
+    /// Validate a `sig_cid` extra field, when present, like `json_cid`
+    pub validate_sig_cid: bool,
+    /// Require the name to be a valid DNS label
+    pub name_must_be_dns_label: bool,
+    /// Maximum number of distinct tags in a `tags` extra field
+    pub max_tags: u32,
+    /// Reject a `tags` extra field that repeats a tag instead of collapsing it
+    pub reject_duplicate_tags: bool,
+    /// Reject pre-release versions such as `1.0.0-beta`
+    pub forbid_prerelease: bool,
+    /// When set and non-empty, require CIDs to start with one of these prefixes
+    pub allowed_cid_prefixes: Option<Vec<Bytes>>,
+    /// Require extra field keys in strictly ascending byte order
+    pub require_sorted_extra: bool,
+    /// Check a `model_size_bytes` extra field, when present, is an integer in `1..=max_model_size`
+    pub validate_model_size: bool,
+    /// Largest accepted `model_size_bytes` extra field value
+    pub max_model_size: u64,
+    /// Reject a description byte-equal to the name
+    pub require_distinct_name_description: bool,
+    /// Lowercase the model hash when normalizing
+    pub lowercase_hash: bool,
+    /// Require a `deprecated` extra field, when present, to be exactly `true` or `false`
+    pub strict_deprecated: bool,
+    /// Reject metadata with no extra fields
+    pub require_extra_fields: bool,
+    /// Minimum number of distinct tags in the `tags` extra field
+    pub min_distinct_tags: u32,
+    /// When set, reject extra fields whose key is not in this list with
+    /// `MetadataError::DisallowedExtraField`
+    pub allowed_extra_keys: Option<Vec<Bytes>>,
+    /// Maximum name length in bytes, on top of the name validator bounds
+    pub max_name_length: u32,
+    /// Maximum description length in bytes, on top of the description validator bounds
+    pub max_description_length: u32,
+    /// Reject an `assets` extra field that lists `json_cid` again
+    pub forbid_redundant_asset_cid: bool,
+    /// Check a `rank` extra field, when present, is an integer within `rank_range`
+    pub validate_rank: bool,
+    /// Inclusive bounds for a `rank` extra field
+    pub rank_range: (u32, u32),
+    /// Reject integer extra field values with redundant leading zeros (`007`)
+    pub reject_leading_zeros: bool,
+    /// Require CIDs in CIDv1 lowercase base32 form
+    pub require_cidv1_base32: bool,
+    /// When set, require the CID codec to be one of these multicodec codes
+    pub allowed_cid_codecs: Option<Vec<u64>>,
+    /// Reject extra fields with an empty value
+    pub forbid_empty_extra_values: bool,
+    /// Output the model hash as its raw digest (see `hash::to_raw`)
+    pub store_raw_hash: bool,
+    /// Reject the placeholder version `0.0.0`
+    pub forbid_zero_version: bool,
+    /// Reject extra field keys that shadow a scalar field key (see `well_known::SCALAR_KEYS`)
+    pub forbid_scalar_key_shadowing: bool,
+    /// Accept standard base64 model hashes that fail the hex hash validator
+    pub accept_base64_hash: bool,
+    /// Maximum number of whitespace-separated words in `description`
+    pub max_description_words: u32,
+    /// Maximum CID references across `json_cid`, `sig_cid` and `assets`
+    pub max_total_cids: u32,
+    /// Check well-known extra field values with `well_known::validate_value`
+    pub type_check_well_known: bool,
+    /// Require the `license` extra field, when present, to pass `well_known::is_valid_spdx`
+    pub require_spdx_license: bool,
+    /// Reject names made up solely of ASCII digits
+    pub forbid_numeric_name: bool,
+    /// Require at least one `assets` CID when `model_size_bytes` is present and not `0`
+    pub require_assets_with_model_size: bool,
+    /// Ledger protocol version from which `validate_for_ledger` requires CIDv1
+    pub strict_cid_protocol_version: u32,
+    /// Reject names containing any non-ASCII byte, a coarse guard against homoglyphs
+    pub ascii_only_name: bool,
+    /// Require every `maintainers` entry to be a hex-encoded 32-byte public key
+    pub validate_maintainers: bool,
+    /// Maximum number of entries in the `maintainers` extra field
+    pub max_maintainers: u32,
+    /// Versioning scheme enforced on `version`
+    pub version_scheme: VersionScheme,
+}
+
+impl Default for ParserConfig {
+    fn default() -> Self {
+        Self {
+            reject_json_blob_cid: false,
+            reject_blank_text: false,
+            validate_created_at: false,
+            created_at_max_skew: 300,
+            require_printable_text: false,
+            require_utf8_description: false,
+            forbid_reserved_keys: false,
+            max_extra_fields: u32::MAX,
+            max_extra_bytes: u32::MAX,
+            max_extra_key_length: 64,
+            min_cid_version: 0,
+            allowed_content_types: None,
+            accept_base58_hash: false,
+            allowed_kinds: None,
+            allow_empty_description: false,
+            validate_asset_cids: false,
+            cidv1_case: CasePolicy::LowerOnly,
+            deep_scan: false,
+            validate_sig_cid: false,
+            name_must_be_dns_label: false,
+            max_tags: u32::MAX,
+            reject_duplicate_tags: false,
+            forbid_prerelease: false,
+            allowed_cid_prefixes: None,
+            require_sorted_extra: false,
+            validate_model_size: false,
+            max_model_size: u64::MAX,
+            require_distinct_name_description: false,
+            lowercase_hash: false,
+            strict_deprecated: false,
+            require_extra_fields: false,
+            min_distinct_tags: 0,
+            allowed_extra_keys: None,
+            max_name_length: u32::MAX,
+            max_description_length: u32::MAX,
+            forbid_redundant_asset_cid: false,
+            validate_rank: false,
+            rank_range: (1, 100),
+            reject_leading_zeros: false,
+            require_cidv1_base32: false,
+            allowed_cid_codecs: None,
+            forbid_empty_extra_values: false,
+            store_raw_hash: false,
+            forbid_zero_version: false,
+            forbid_scalar_key_shadowing: false,
+            accept_base64_hash: false,
+            max_description_words: u32::MAX,
+            max_total_cids: u32::MAX,
+            type_check_well_known: false,
+            require_spdx_license: false,
+            forbid_numeric_name: false,
+            require_assets_with_model_size: false,
+            strict_cid_protocol_version: u32::MAX,
+            ascii_only_name: false,
+            validate_maintainers: false,
+            max_maintainers: u32::MAX,
+            version_scheme: VersionScheme::Any,
+        }
+    }
+}
+
+impl ParserConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Locked-down preset for production registries
+    ///
+    /// Requires printable text, a UTF-8 description and CIDv1, rejects JSON
+    /// blobs pasted as CIDs, forbids reserved keys and caps extra fields at
+    /// 32 entries / 4 KiB.
+    pub fn strict_production() -> Self {
+        Self {
+            reject_json_blob_cid: true,
+            require_printable_text: true,
+            require_utf8_description: true,
+            forbid_reserved_keys: true,
+            max_extra_fields: 32,
+            max_extra_bytes: 4096,
+            min_cid_version: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Preset matching what IPFS HTTP gateways serve
+    ///
+    /// CIDv1 in lowercase base32 with the dag-pb or raw codec; JSON blobs
+    /// pasted as CIDs are rejected.
+    pub fn ipfs_gateway(env: &Env) -> Self {
+        Self {
+            reject_json_blob_cid: true,
+            min_cid_version: 1,
+            cidv1_case: CasePolicy::LowerOnly,
+            require_cidv1_base32: true,
+            allowed_cid_codecs: Some(Vec::from_array(env, [cid::DAG_PB, cid::RAW])),
+            ..Self::default()
+        }
+    }
+
+    pub fn reject_json_blob_cid(mut self, reject: bool) -> Self {
+        self.reject_json_blob_cid = reject;
+        self
+    }
+
+    pub fn reject_blank_text(mut self, reject: bool) -> Self {
+        self.reject_blank_text = reject;
+        self
+    }
+
+    pub fn validate_created_at(mut self, validate: bool, max_skew: u64) -> Self {
+        self.validate_created_at = validate;
+        self.created_at_max_skew = max_skew;
+        self
+    }
+
+    pub fn require_printable_text(mut self, require: bool) -> Self {
+        self.require_printable_text = require;
+        self
+    }
+
+    pub fn require_utf8_description(mut self, require: bool) -> Self {
+        self.require_utf8_description = require;
+        self
+    }
+
+    pub fn forbid_reserved_keys(mut self, forbid: bool) -> Self {
+        self.forbid_reserved_keys = forbid;
+        self
+    }
+
+    pub fn with_extra_limits(mut self, max_fields: u32, max_bytes: u32) -> Self {
+        self.max_extra_fields = max_fields;
+        self.max_extra_bytes = max_bytes;
+        self
+    }
+
+    pub fn max_extra_key_length(mut self, max: u32) -> Self {
+        self.max_extra_key_length = max;
+        self
+    }
+
+    pub fn min_cid_version(mut self, version: u32) -> Self {
+        self.min_cid_version = version;
+        self
+    }
+
+    pub fn with_allowed_content_types(mut self, content_types: Vec<Bytes>) -> Self {
+        self.allowed_content_types = Some(content_types);
+        self
+    }
+
+    pub fn accept_base58_hash(mut self, accept: bool) -> Self {
+        self.accept_base58_hash = accept;
+        self
+    }
+
+    pub fn with_allowed_kinds(mut self, kinds: Vec<Bytes>) -> Self {
+        self.allowed_kinds = Some(kinds);
+        self
+    }
+
+    pub fn allow_empty_description(mut self, allow: bool) -> Self {
+        self.allow_empty_description = allow;
+        self
+    }
+
+    pub fn validate_asset_cids(mut self, validate: bool) -> Self {
+        self.validate_asset_cids = validate;
+        self
+    }
+
+    pub fn cidv1_case(mut self, policy: CasePolicy) -> Self {
+        self.cidv1_case = policy;
+        self
+    }
+
+    pub fn deep_scan(mut self, deep: bool) -> Self {
+        self.deep_scan = deep;
+        self
+    }
+
+    pub fn validate_sig_cid(mut self, validate: bool) -> Self {
+        self.validate_sig_cid = validate;
+        self
+    }
+
+    pub fn name_must_be_dns_label(mut self, require: bool) -> Self {
+        self.name_must_be_dns_label = require;
+        self
+    }
+
+    pub fn max_tags(mut self, max: u32) -> Self {
+        self.max_tags = max;
+        self
+    }
+
+    pub fn reject_duplicate_tags(mut self, reject: bool) -> Self {
+        self.reject_duplicate_tags = reject;
+        self
+    }
+
+    pub fn forbid_prerelease(mut self, forbid: bool) -> Self {
+        self.forbid_prerelease = forbid;
+        self
+    }
+
+    pub fn with_allowed_cid_prefixes(mut self, prefixes: Vec<Bytes>) -> Self {
+        self.allowed_cid_prefixes = Some(prefixes);
+        self
+    }
+
+    pub fn require_sorted_extra(mut self, require: bool) -> Self {
+        self.require_sorted_extra = require;
+        self
+    }
+
+    pub fn validate_model_size(mut self, validate: bool) -> Self {
+        self.validate_model_size = validate;
+        self
+    }
+
+    pub fn max_model_size(mut self, max: u64) -> Self {
+        self.max_model_size = max;
+        self
+    }
+
+    pub fn require_distinct_name_description(mut self, require: bool) -> Self {
+        self.require_distinct_name_description = require;
+        self
+    }
+
+    pub fn lowercase_hash(mut self, lowercase: bool) -> Self {
+        self.lowercase_hash = lowercase;
+        self
+    }
+
+    pub fn strict_deprecated(mut self, strict: bool) -> Self {
+        self.strict_deprecated = strict;
+        self
+    }
+
+    pub fn require_extra_fields(mut self, require: bool) -> Self {
+        self.require_extra_fields = require;
+        self
+    }
+
+    pub fn min_distinct_tags(mut self, min: u32) -> Self {
+        self.min_distinct_tags = min;
+        self
+    }
+
+    pub fn with_allowed_extra_keys(mut self, keys: Vec<Bytes>) -> Self {
+        self.allowed_extra_keys = Some(keys);
+        self
+    }
+
+    pub fn max_name_length(mut self, max: u32) -> Self {
+        self.max_name_length = max;
+        self
+    }
+
+    pub fn max_description_length(mut self, max: u32) -> Self {
+        self.max_description_length = max;
+        self
+    }
+
+    pub fn forbid_redundant_asset_cid(mut self, forbid: bool) -> Self {
+        self.forbid_redundant_asset_cid = forbid;
+        self
+    }
+
+    pub fn validate_rank(mut self, validate: bool) -> Self {
+        self.validate_rank = validate;
+        self
+    }
+
+    pub fn rank_range(mut self, min: u32, max: u32) -> Self {
+        self.rank_range = (min, max);
+        self
+    }
+
+    pub fn reject_leading_zeros(mut self, reject: bool) -> Self {
+        self.reject_leading_zeros = reject;
+        self
+    }
+
+    pub fn require_cidv1_base32(mut self, require: bool) -> Self {
+        self.require_cidv1_base32 = require;
+        self
+    }
+
+    pub fn with_allowed_cid_codecs(mut self, codecs: Vec<u64>) -> Self {
+        self.allowed_cid_codecs = Some(codecs);
+        self
+    }
+
+    pub fn forbid_empty_extra_values(mut self, forbid: bool) -> Self {
+        self.forbid_empty_extra_values = forbid;
+        self
+    }
+
+    pub fn store_raw_hash(mut self, raw: bool) -> Self {
+        self.store_raw_hash = raw;
+        self
+    }
+
+    pub fn forbid_zero_version(mut self, forbid: bool) -> Self {
+        self.forbid_zero_version = forbid;
+        self
+    }
+
+    pub fn forbid_scalar_key_shadowing(mut self, forbid: bool) -> Self {
+        self.forbid_scalar_key_shadowing = forbid;
+        self
+    }
+
+    pub fn accept_base64_hash(mut self, accept: bool) -> Self {
+        self.accept_base64_hash = accept;
+        self
+    }
+
+    pub fn max_description_words(mut self, max: u32) -> Self {
+        self.max_description_words = max;
+        self
+    }
+
+    pub fn max_total_cids(mut self, max: u32) -> Self {
+        self.max_total_cids = max;
+        self
+    }
+
+    pub fn type_check_well_known(mut self, check: bool) -> Self {
+        self.type_check_well_known = check;
+        self
+    }
+
+    pub fn require_spdx_license(mut self, require: bool) -> Self {
+        self.require_spdx_license = require;
+        self
+    }
+
+    pub fn forbid_numeric_name(mut self, forbid: bool) -> Self {
+        self.forbid_numeric_name = forbid;
+        self
+    }
+
+    pub fn require_assets_with_model_size(mut self, require: bool) -> Self {
+        self.require_assets_with_model_size = require;
+        self
+    }
+
+    pub fn strict_cid_protocol_version(mut self, version: u32) -> Self {
+        self.strict_cid_protocol_version = version;
+        self
+    }
+
+    pub fn ascii_only_name(mut self, ascii_only: bool) -> Self {
+        self.ascii_only_name = ascii_only;
+        self
+    }
+
+    pub fn validate_maintainers(mut self, validate: bool) -> Self {
+        self.validate_maintainers = validate;
+        self
+    }
+
+    pub fn max_maintainers(mut self, max: u32) -> Self {
+        self.max_maintainers = max;
+        self
+    }
+
+    pub fn version_scheme(mut self, scheme: VersionScheme) -> Self {
+        self.version_scheme = scheme;
+        self
+    }
+
+    /// Encode the limit profile as a fixed 83-byte layout
+    ///
+    /// All integers are big-endian:
+    ///
+    /// | offset | size | field                         |
+    /// |--------|------|-------------------------------|
+    /// | 0      | 1    | `PROFILE_VERSION`             |
+    /// | 1      | 4    | `max_extra_fields`            |
+    /// | 5      | 4    | `max_extra_bytes`             |
+    /// | 9      | 4    | `max_extra_key_length`        |
+    /// | 13     | 4    | `min_cid_version`             |
+    /// | 17     | 8    | `created_at_max_skew`         |
+    /// | 25     | 8    | flags bitset (every `bool`)   |
+    /// | 33     | 4    | `max_name_length`             |
+    /// | 37     | 4    | `max_description_length`      |
+    /// | 41     | 4    | `max_tags`                    |
+    /// | 45     | 4    | `min_distinct_tags`           |
+    /// | 49     | 8    | `rank_range` (min, max)       |
+    /// | 57     | 8    | `max_model_size`              |
+    /// | 65     | 4    | `max_description_words`       |
+    /// | 69     | 4    | `max_total_cids`              |
+    /// | 73     | 4    | `strict_cid_protocol_version` |
+    /// | 77     | 4    | `max_maintainers`             |
+    /// | 81     | 1    | `cidv1_case`                  |
+    /// | 82     | 1    | `version_scheme`              |
+    ///
+    /// Allowlists (`allowed_content_types`, `allowed_kinds`, ...) are not part of
+    /// the profile; a config with any allowlist set fails with
+    /// `MetadataError::InvalidStructure` rather than being encoded lossily.
+    pub fn to_bytes(&self, env: &Env) -> Result<Bytes, MetadataError> {
+        if
+            self.allowed_content_types.is_some() ||
+            self.allowed_kinds.is_some() ||
+            self.allowed_cid_prefixes.is_some() ||
+            self.allowed_extra_keys.is_some() ||
+            self.allowed_cid_codecs.is_some()
+        {
+            return Err(MetadataError::InvalidStructure);
+        }
+
+        let flags = [
+            (self.reject_json_blob_cid, FLAG_REJECT_JSON_BLOB_CID),
+            (self.reject_blank_text, FLAG_REJECT_BLANK_TEXT),
+            (self.validate_created_at, FLAG_VALIDATE_CREATED_AT),
+            (self.require_printable_text, FLAG_REQUIRE_PRINTABLE_TEXT),
+            (self.require_utf8_description, FLAG_REQUIRE_UTF8_DESCRIPTION),
+            (self.forbid_reserved_keys, FLAG_FORBID_RESERVED_KEYS),
+            (self.accept_base58_hash, FLAG_ACCEPT_BASE58_HASH),
+            (self.allow_empty_description, FLAG_ALLOW_EMPTY_DESCRIPTION),
+            (self.validate_asset_cids, FLAG_VALIDATE_ASSET_CIDS),
+            (self.deep_scan, FLAG_DEEP_SCAN),
+            (self.validate_sig_cid, FLAG_VALIDATE_SIG_CID),
+            (self.name_must_be_dns_label, FLAG_NAME_MUST_BE_DNS_LABEL),
+            (self.reject_duplicate_tags, FLAG_REJECT_DUPLICATE_TAGS),
+            (self.forbid_prerelease, FLAG_FORBID_PRERELEASE),
+            (self.require_sorted_extra, FLAG_REQUIRE_SORTED_EXTRA),
+            (self.validate_model_size, FLAG_VALIDATE_MODEL_SIZE),
+            (self.require_distinct_name_description, FLAG_REQUIRE_DISTINCT_NAME_DESCRIPTION),
+            (self.lowercase_hash, FLAG_LOWERCASE_HASH),
+            (self.strict_deprecated, FLAG_STRICT_DEPRECATED),
+            (self.require_extra_fields, FLAG_REQUIRE_EXTRA_FIELDS),
+            (self.forbid_redundant_asset_cid, FLAG_FORBID_REDUNDANT_ASSET_CID),
+            (self.validate_rank, FLAG_VALIDATE_RANK),
+            (self.reject_leading_zeros, FLAG_REJECT_LEADING_ZEROS),
+            (self.require_cidv1_base32, FLAG_REQUIRE_CIDV1_BASE32),
+            (self.forbid_empty_extra_values, FLAG_FORBID_EMPTY_EXTRA_VALUES),
+            (self.store_raw_hash, FLAG_STORE_RAW_HASH),
+            (self.forbid_zero_version, FLAG_FORBID_ZERO_VERSION),
+            (self.forbid_scalar_key_shadowing, FLAG_FORBID_SCALAR_KEY_SHADOWING),
+            (self.accept_base64_hash, FLAG_ACCEPT_BASE64_HASH),
+            (self.type_check_well_known, FLAG_TYPE_CHECK_WELL_KNOWN),
+            (self.require_spdx_license, FLAG_REQUIRE_SPDX_LICENSE),
+            (self.forbid_numeric_name, FLAG_FORBID_NUMERIC_NAME),
+            (self.require_assets_with_model_size, FLAG_REQUIRE_ASSETS_WITH_MODEL_SIZE),
+            (self.ascii_only_name, FLAG_ASCII_ONLY_NAME),
+            (self.validate_maintainers, FLAG_VALIDATE_MAINTAINERS),
+        ]
+        .iter()
+        .filter(|(set, _)| *set)
+        .fold(0u64, |acc, (_, flag)| acc | flag);
+
+        let mut out = Bytes::new(env);
+        out.push_back(PROFILE_VERSION);
+        out.extend_from_slice(&self.max_extra_fields.to_be_bytes());
+        out.extend_from_slice(&self.max_extra_bytes.to_be_bytes());
+        out.extend_from_slice(&self.max_extra_key_length.to_be_bytes());
+        out.extend_from_slice(&self.min_cid_version.to_be_bytes());
+        out.extend_from_slice(&self.created_at_max_skew.to_be_bytes());
+        out.extend_from_slice(&flags.to_be_bytes());
+        out.extend_from_slice(&self.max_name_length.to_be_bytes());
+        out.extend_from_slice(&self.max_description_length.to_be_bytes());
+        out.extend_from_slice(&self.max_tags.to_be_bytes());
+        out.extend_from_slice(&self.min_distinct_tags.to_be_bytes());
+        out.extend_from_slice(&self.rank_range.0.to_be_bytes());
+        out.extend_from_slice(&self.rank_range.1.to_be_bytes());
+        out.extend_from_slice(&self.max_model_size.to_be_bytes());
+        out.extend_from_slice(&self.max_description_words.to_be_bytes());
+        out.extend_from_slice(&self.max_total_cids.to_be_bytes());
+        out.extend_from_slice(&self.strict_cid_protocol_version.to_be_bytes());
+        out.extend_from_slice(&self.max_maintainers.to_be_bytes());
+        out.push_back(match self.cidv1_case {
+            CasePolicy::LowerOnly => 0,
+            CasePolicy::UpperOnly => 1,
+            CasePolicy::Either => 2,
+        });
+        out.push_back(match self.version_scheme {
+            VersionScheme::SemVer => 0,
+            VersionScheme::CalVer => 1,
+            VersionScheme::Any => 2,
+        });
+        Ok(out)
+    }
+
+    /// Decode a limit profile produced by `to_bytes`
+    ///
+    /// Allowlists take their default (unset) values. Input of the wrong
+    /// length, an unknown version, unknown flag bits or an unknown enum byte is
+    /// rejected with `MetadataError::InvalidStructure`.
+    pub fn from_bytes(_env: &Env, data: &Bytes) -> Result<ParserConfig, MetadataError> {
+        if data.len() != PROFILE_LENGTH || data.get(0) != Some(PROFILE_VERSION) {
+            return Err(MetadataError::InvalidStructure);
+        }
+
+        let read_u32 = |offset: u32| {
+            let mut buf = [0u8; 4];
+            data.slice(offset..offset + 4).copy_into_slice(&mut buf);
+            u32::from_be_bytes(buf)
+        };
+        let read_u64 = |offset: u32| {
+            let mut buf = [0u8; 8];
+            data.slice(offset..offset + 8).copy_into_slice(&mut buf);
+            u64::from_be_bytes(buf)
+        };
+
+        let flags = read_u64(25);
+        if flags & !KNOWN_FLAGS != 0 {
+            return Err(MetadataError::InvalidStructure);
+        }
+        let cidv1_case = match data.get_unchecked(81) {
+            0 => CasePolicy::LowerOnly,
+            1 => CasePolicy::UpperOnly,
+            2 => CasePolicy::Either,
+            _ => {
+                return Err(MetadataError::InvalidStructure);
+            }
+        };
+        let version_scheme = match data.get_unchecked(82) {
+            0 => VersionScheme::SemVer,
+            1 => VersionScheme::CalVer,
+            2 => VersionScheme::Any,
+            _ => {
+                return Err(MetadataError::InvalidStructure);
+            }
+        };
+
+        Ok(ParserConfig {
+            max_extra_fields: read_u32(1),
+            max_extra_bytes: read_u32(5),
+            max_extra_key_length: read_u32(9),
+            min_cid_version: read_u32(13),
+            created_at_max_skew: read_u64(17),
+            max_name_length: read_u32(33),
+            max_description_length: read_u32(37),
+            max_tags: read_u32(41),
+            min_distinct_tags: read_u32(45),
+            rank_range: (read_u32(49), read_u32(53)),
+            max_model_size: read_u64(57),
+            max_description_words: read_u32(65),
+            max_total_cids: read_u32(69),
+            strict_cid_protocol_version: read_u32(73),
+            max_maintainers: read_u32(77),
+            cidv1_case,
+            version_scheme,
+            reject_json_blob_cid: flags & FLAG_REJECT_JSON_BLOB_CID != 0,
+            reject_blank_text: flags & FLAG_REJECT_BLANK_TEXT != 0,
+            validate_created_at: flags & FLAG_VALIDATE_CREATED_AT != 0,
+            require_printable_text: flags & FLAG_REQUIRE_PRINTABLE_TEXT != 0,
+            require_utf8_description: flags & FLAG_REQUIRE_UTF8_DESCRIPTION != 0,
+            forbid_reserved_keys: flags & FLAG_FORBID_RESERVED_KEYS != 0,
+            accept_base58_hash: flags & FLAG_ACCEPT_BASE58_HASH != 0,
+            allow_empty_description: flags & FLAG_ALLOW_EMPTY_DESCRIPTION != 0,
+            validate_asset_cids: flags & FLAG_VALIDATE_ASSET_CIDS != 0,
+            deep_scan: flags & FLAG_DEEP_SCAN != 0,
+            validate_sig_cid: flags & FLAG_VALIDATE_SIG_CID != 0,
+            name_must_be_dns_label: flags & FLAG_NAME_MUST_BE_DNS_LABEL != 0,
+            reject_duplicate_tags: flags & FLAG_REJECT_DUPLICATE_TAGS != 0,
+            forbid_prerelease: flags & FLAG_FORBID_PRERELEASE != 0,
+            require_sorted_extra: flags & FLAG_REQUIRE_SORTED_EXTRA != 0,
+            validate_model_size: flags & FLAG_VALIDATE_MODEL_SIZE != 0,
+            require_distinct_name_description: flags & FLAG_REQUIRE_DISTINCT_NAME_DESCRIPTION != 0,
+            lowercase_hash: flags & FLAG_LOWERCASE_HASH != 0,
+            strict_deprecated: flags & FLAG_STRICT_DEPRECATED != 0,
+            require_extra_fields: flags & FLAG_REQUIRE_EXTRA_FIELDS != 0,
+            forbid_redundant_asset_cid: flags & FLAG_FORBID_REDUNDANT_ASSET_CID != 0,
+            validate_rank: flags & FLAG_VALIDATE_RANK != 0,
+            reject_leading_zeros: flags & FLAG_REJECT_LEADING_ZEROS != 0,
+            require_cidv1_base32: flags & FLAG_REQUIRE_CIDV1_BASE32 != 0,
+            forbid_empty_extra_values: flags & FLAG_FORBID_EMPTY_EXTRA_VALUES != 0,
+            store_raw_hash: flags & FLAG_STORE_RAW_HASH != 0,
+            forbid_zero_version: flags & FLAG_FORBID_ZERO_VERSION != 0,
+            forbid_scalar_key_shadowing: flags & FLAG_FORBID_SCALAR_KEY_SHADOWING != 0,
+            accept_base64_hash: flags & FLAG_ACCEPT_BASE64_HASH != 0,
+            type_check_well_known: flags & FLAG_TYPE_CHECK_WELL_KNOWN != 0,
+            require_spdx_license: flags & FLAG_REQUIRE_SPDX_LICENSE != 0,
+            forbid_numeric_name: flags & FLAG_FORBID_NUMERIC_NAME != 0,
+            require_assets_with_model_size: flags & FLAG_REQUIRE_ASSETS_WITH_MODEL_SIZE != 0,
+            ascii_only_name: flags & FLAG_ASCII_ONLY_NAME != 0,
+            validate_maintainers: flags & FLAG_VALIDATE_MAINTAINERS != 0,
+            ..ParserConfig::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_round_trip() {
+        let env = Env::default();
+        let config = ParserConfig::strict_production()
+            .reject_blank_text(true)
+            .validate_created_at(true, 120)
+            .max_extra_key_length(48)
+            .max_name_length(40)
+            .max_description_length(500)
+            .require_cidv1_base32(true)
+            .deep_scan(true)
+            .validate_maintainers(true)
+            .cidv1_case(CasePolicy::Either)
+            .version_scheme(VersionScheme::CalVer);
+
+        let encoded = config.to_bytes(&env).unwrap();
+        assert_eq!(encoded.len(), PROFILE_LENGTH);
+
+        let decoded = ParserConfig::from_bytes(&env, &encoded).unwrap();
+        assert_eq!(decoded.max_extra_fields, 32);
+        assert_eq!(decoded.max_extra_bytes, 4096);
+        assert_eq!(decoded.max_extra_key_length, 48);
+        assert_eq!(decoded.min_cid_version, 1);
+        assert_eq!(decoded.created_at_max_skew, 120);
+        assert!(decoded.reject_json_blob_cid);
+        assert!(decoded.reject_blank_text);
+        assert!(decoded.validate_created_at);
+        assert!(decoded.require_printable_text);
+        assert!(decoded.require_utf8_description);
+        assert!(decoded.forbid_reserved_keys);
+        assert!(!decoded.accept_base58_hash);
+        assert_eq!(decoded.max_name_length, 40);
+        assert_eq!(decoded.max_description_length, 500);
+        assert!(decoded.require_cidv1_base32);
+        assert!(decoded.deep_scan);
+        assert!(decoded.validate_maintainers);
+        assert_eq!(decoded.cidv1_case, CasePolicy::Either);
+        assert_eq!(decoded.version_scheme, VersionScheme::CalVer);
+        assert_eq!(decoded.to_bytes(&env).unwrap(), encoded);
+    }
+
+    #[test]
+    fn test_profile_rejects_malformed_input() {
+        let env = Env::default();
+        let encoded = ParserConfig::default().to_bytes(&env).unwrap();
+
+        let truncated = encoded.slice(0..PROFILE_LENGTH - 1);
+        assert_eq!(
+            ParserConfig::from_bytes(&env, &truncated).unwrap_err(),
+            MetadataError::InvalidStructure
+        );
+
+        let mut bumped = encoded.clone();
+        bumped.set(0, PROFILE_VERSION + 1);
+        assert_eq!(
+            ParserConfig::from_bytes(&env, &bumped).unwrap_err(),
+            MetadataError::InvalidStructure
+        );
+
+        let mut unknown_flag = encoded.clone();
+        unknown_flag.set(25, 0x80);
+        assert_eq!(
+            ParserConfig::from_bytes(&env, &unknown_flag).unwrap_err(),
+            MetadataError::InvalidStructure
+        );
+
+        let mut unknown_case = encoded;
+        unknown_case.set(81, 3);
+        assert_eq!(
+            ParserConfig::from_bytes(&env, &unknown_case).unwrap_err(),
+            MetadataError::InvalidStructure
+        );
+    }
+
+    #[test]
+    fn test_profile_rejects_allowlists() {
+        let env = Env::default();
+        let kinds = Vec::from_array(&env, [Bytes::from_slice(&env, b"trader")]);
+
+        assert_eq!(
+            ParserConfig::new().with_allowed_kinds(kinds).to_bytes(&env),
+            Err(MetadataError::InvalidStructure)
+        );
+    }
+}
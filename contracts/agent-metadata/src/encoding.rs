@@ -0,0 +1,217 @@
+//! Length-prefixed binary encoding used for fingerprints and storage sizing.
+//!
+//! Every field is written as a 4-byte big-endian length followed by its bytes,
+//! so no field boundary can be forged by shifting bytes between neighbours.
+
+use soroban_sdk::{ Bytes, Env, Vec };
+
+use crate::MetadataError;
+
+/// Size of a length or count prefix
+pub const PREFIX_SIZE: u32 = 4;
+
+/// Size of the leading format version byte
+pub const VERSION_SIZE: u32 = 1;
+
+/// Append `value` to `out` with a 4-byte big-endian length prefix
+pub fn push_field(out: &mut Bytes, value: &Bytes) {
+    out.extend_from_slice(&value.len().to_be_bytes());
+    out.append(value);
+}
+
+/// Encode extra fields in the given order as a count followed by
+/// length-prefixed key/value pairs
+pub fn encode_extra_fields(env: &Env, fields: &Vec<(Bytes, Bytes)>) -> Bytes {
+    let mut out = Bytes::new(env);
+    out.extend_from_slice(&fields.len().to_be_bytes());
+    for (key, value) in fields.iter() {
+        push_field(&mut out, &key);
+        push_field(&mut out, &value);
+    }
+    out
+}
+
+/// Encoded size of `value` as written by `push_field`
+pub fn field_size(value: &Bytes) -> u32 {
+    PREFIX_SIZE.saturating_add(value.len())
+}
+
+/// Shortest run of one byte worth encoding as a repeat
+const MIN_RUN: u32 = 3;
+
+/// Longest run a single repeat control byte can express
+const MAX_RUN: u32 = MIN_RUN + 0x7F;
+
+/// Longest literal span a single control byte can express
+const MAX_LITERAL: u32 = 0x80;
+
+/// Run-length encode `data`
+///
+/// A control byte below `0x80` is followed by `control + 1` literal bytes; a
+/// control byte `c` at or above `0x80` is followed by one byte to repeat
+/// `c - 0x80 + 3` times. Runs shorter than 3 stay literal, so the output is
+/// deterministic for a given input.
+pub fn rle_compress(env: &Env, data: &Bytes) -> Bytes {
+    let mut out = Bytes::new(env);
+    let len = data.len();
+    let mut literal_start = 0;
+    let mut i = 0;
+
+    while i < len {
+        let b = data.get_unchecked(i);
+        let mut run = 1;
+        while i + run < len && run < MAX_RUN && data.get_unchecked(i + run) == b {
+            run += 1;
+        }
+
+        if run >= MIN_RUN {
+            push_literals(&mut out, data, literal_start, i);
+            out.push_back((0x80 + run - MIN_RUN) as u8);
+            out.push_back(b);
+            literal_start = i + run;
+        }
+        i += run;
+    }
+    push_literals(&mut out, data, literal_start, len);
+
+    out
+}
+
+fn push_literals(out: &mut Bytes, data: &Bytes, mut start: u32, end: u32) {
+    while start < end {
+        let n = (end - start).min(MAX_LITERAL);
+        out.push_back((n - 1) as u8);
+        out.append(&data.slice(start..start + n));
+        start += n;
+    }
+}
+
+/// Decode output of `rle_compress`
+///
+/// A truncated literal span or repeat fails with
+/// `MetadataError::InvalidStructure`.
+pub fn rle_decompress(env: &Env, data: &Bytes) -> Result<Bytes, MetadataError> {
+    let mut out = Bytes::new(env);
+    let mut i = 0;
+
+    while i < data.len() {
+        let control = data.get_unchecked(i) as u32;
+        i += 1;
+        if control < 0x80 {
+            let end = i + control + 1;
+            if end > data.len() {
+                return Err(MetadataError::InvalidStructure);
+            }
+            out.append(&data.slice(i..end));
+            i = end;
+        } else {
+            let b = data.get(i).ok_or(MetadataError::InvalidStructure)?;
+            for _ in 0..control - 0x80 + MIN_RUN {
+                out.push_back(b);
+            }
+            i += 1;
+        }
+    }
+
+    Ok(out)
+}
+
+/// Cursor over a length-prefixed encoding
+///
+/// Every read fails with `MetadataError::InvalidStructure` if it would run
+/// past the end of the input.
+pub struct Reader {
+    data: Bytes,
+    offset: u32,
+}
+
+impl Reader {
+    pub fn new(data: Bytes) -> Self {
+        Self { data, offset: 0 }
+    }
+
+    /// Read a single byte
+    pub fn read_u8(&mut self) -> Result<u8, MetadataError> {
+        let byte = self.data.get(self.offset).ok_or(MetadataError::InvalidStructure)?;
+        self.offset += 1;
+        Ok(byte)
+    }
+
+    /// Read a 4-byte big-endian integer
+    pub fn read_u32(&mut self) -> Result<u32, MetadataError> {
+        let end = self.offset.checked_add(PREFIX_SIZE).ok_or(MetadataError::InvalidStructure)?;
+        if end > self.data.len() {
+            return Err(MetadataError::InvalidStructure);
+        }
+
+        let mut buf = [0u8; 4];
+        self.data.slice(self.offset..end).copy_into_slice(&mut buf);
+        self.offset = end;
+        Ok(u32::from_be_bytes(buf))
+    }
+
+    /// Read a field written by `push_field`
+    pub fn read_field(&mut self) -> Result<Bytes, MetadataError> {
+        let len = self.read_u32()?;
+        let end = self.offset.checked_add(len).ok_or(MetadataError::InvalidStructure)?;
+        if end > self.data.len() {
+            return Err(MetadataError::InvalidStructure);
+        }
+
+        let field = self.data.slice(self.offset..end);
+        self.offset = end;
+        Ok(field)
+    }
+
+    /// Read extra fields written by `encode_extra_fields`
+    pub fn read_extra_fields(&mut self, env: &Env) -> Result<Vec<(Bytes, Bytes)>, MetadataError> {
+        let count = self.read_u32()?;
+        let mut fields = Vec::new(env);
+        for _ in 0..count {
+            let key = self.read_field()?;
+            let value = self.read_field()?;
+            fields.push_back((key, value));
+        }
+        Ok(fields)
+    }
+
+    /// Fail unless every byte has been consumed
+    pub fn finish(self) -> Result<(), MetadataError> {
+        if self.offset == self.data.len() {
+            Ok(())
+        } else {
+            Err(MetadataError::InvalidStructure)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_push_field_prefixes_length() {
+        let env = Env::default();
+        let mut out = Bytes::new(&env);
+
+        push_field(&mut out, &Bytes::from_slice(&env, b"abc"));
+
+        assert_eq!(out, Bytes::from_slice(&env, &[0, 0, 0, 3, b'a', b'b', b'c']));
+    }
+
+    #[test]
+    fn test_reader_round_trip_and_truncation() {
+        let env = Env::default();
+        let mut out = Bytes::new(&env);
+        push_field(&mut out, &Bytes::from_slice(&env, b"abc"));
+        push_field(&mut out, &Bytes::new(&env));
+
+        let mut reader = Reader::new(out.clone());
+        assert_eq!(reader.read_field(), Ok(Bytes::from_slice(&env, b"abc")));
+        assert_eq!(reader.read_field(), Ok(Bytes::new(&env)));
+        assert_eq!(reader.finish(), Ok(()));
+
+        let mut truncated = Reader::new(out.slice(0..5));
+        assert_eq!(truncated.read_field(), Err(MetadataError::InvalidStructure));
+    }
+}
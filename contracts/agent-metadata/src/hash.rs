@@ -0,0 +1,310 @@
+//! Decoding helpers for model hash digests.
+//!
+//! Hashes are submitted as text; these helpers recover the raw digest bytes
+//! from the encodings integrators use in practice.
+
+use common_utils::validator::{ HASH_MAX_LENGTH, HASH_MIN_LENGTH };
+use soroban_sdk::{ Bytes, Env };
+
+use crate::MetadataError;
+
+/// Host hash function selector
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HashAlgo {
+    Sha256,
+    Keccak256,
+}
+
+/// Hash `data` with `algo` on the host, returning the 32-byte digest
+pub fn digest(env: &Env, algo: HashAlgo, data: &Bytes) -> Bytes {
+    let digest = match algo {
+        HashAlgo::Sha256 => env.crypto().sha256(data),
+        HashAlgo::Keccak256 => env.crypto().keccak256(data),
+    };
+    Bytes::from_array(env, &digest.to_array())
+}
+
+/// Lowercase hex digits
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Bitcoin base58 alphabet (no `0`, `O`, `I` or `l`)
+const BASE58_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Longest base58 input accepted, bounding the fixed decode buffer
+const MAX_BASE58_INPUT: u32 = 128;
+
+/// Decode buffer size; 128 base58 characters need at most 94 bytes
+const BASE58_BUFFER: usize = 96;
+
+/// Shortest hash accepted by the default `HashValidator`
+pub const MIN_LENGTH: usize = HASH_MIN_LENGTH;
+
+/// Longest hash accepted by the default `HashValidator`
+pub const MAX_LENGTH: usize = HASH_MAX_LENGTH;
+
+/// Length of a raw SHA-256 digest in bytes
+pub const RAW_DIGEST_LENGTH: u32 = 32;
+
+fn hex_value(c: u8) -> Option<u8> {
+    match c {
+        b'0'..=b'9' => Some(c - b'0'),
+        b'a'..=b'f' => Some(c - b'a' + 10),
+        b'A'..=b'F' => Some(c - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Whether `c` is in the base58btc alphabet
+pub fn is_base58_char(c: u8) -> bool {
+    base58_value(c).is_some()
+}
+
+fn base58_value(c: u8) -> Option<u8> {
+    BASE58_ALPHABET.iter().position(|&a| a == c).map(|i| i as u8)
+}
+
+fn base64_value(c: u8) -> Option<u8> {
+    match c {
+        b'A'..=b'Z' => Some(c - b'A'),
+        b'a'..=b'z' => Some(c - b'a' + 26),
+        b'0'..=b'9' => Some(c - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+/// Env-free check of a hash against the default validator rules
+///
+/// Mirrors `MetadataValidator::new().validate_model_hash`, which only bounds
+/// the length in its default lenient mode.
+pub fn is_valid_hash(hash: &[u8]) -> bool {
+    (MIN_LENGTH..=MAX_LENGTH).contains(&hash.len())
+}
+
+/// Env-free hash validation returning the crate error code
+pub fn validate(hash: &[u8]) -> Result<(), MetadataError> {
+    if is_valid_hash(hash) {
+        Ok(())
+    } else {
+        Err(MetadataError::HashVerificationFailed)
+    }
+}
+
+/// Decode a hex string (either case) into raw bytes
+///
+/// Returns `None` for empty or odd-length input or any non-hex character.
+pub fn decode_hex(env: &Env, input: &Bytes) -> Option<Bytes> {
+    if input.is_empty() || input.len() % 2 != 0 {
+        return None;
+    }
+
+    let mut out = Bytes::new(env);
+    let mut i = 0;
+    while i < input.len() {
+        let high = hex_value(input.get_unchecked(i))?;
+        let low = hex_value(input.get_unchecked(i + 1))?;
+        out.push_back((high << 4) | low);
+        i += 2;
+    }
+
+    Some(out)
+}
+
+/// Compare two digests in time independent of where they first differ
+///
+/// Lengths are compared up front; digest lengths are not secret.
+pub fn ct_eq(a: &Bytes, b: &Bytes) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b.iter()).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+/// Encode raw bytes as lowercase hex
+pub fn encode_hex(env: &Env, data: &[u8]) -> Bytes {
+    let mut out = Bytes::new(env);
+    for &b in data {
+        out.push_back(HEX_DIGITS[(b >> 4) as usize]);
+        out.push_back(HEX_DIGITS[(b & 0x0f) as usize]);
+    }
+    out
+}
+
+/// Canonicalize a hash to its raw digest for compact storage
+///
+/// Length decides the form: an input of exactly `RAW_DIGEST_LENGTH` bytes is
+/// taken to be raw already and returned untouched; anything else must be
+/// hex and is decoded. A 32-character hex string is therefore treated as raw,
+/// which is why only 64-character SHA-256 hex is worth converting. Input that
+/// is neither fails with `HashVerificationFailed`.
+pub fn to_raw(env: &Env, hash: &Bytes) -> Result<Bytes, MetadataError> {
+    if hash.len() == RAW_DIGEST_LENGTH {
+        return Ok(hash.clone());
+    }
+    decode_hex(env, hash).ok_or(MetadataError::HashVerificationFailed)
+}
+
+/// Decode a base58btc string into raw bytes
+///
+/// Leading `1` characters map to leading zero bytes. Returns `None` for
+/// empty input, input over 128 characters or any character outside the
+/// alphabet.
+pub fn decode_base58(env: &Env, input: &Bytes) -> Option<Bytes> {
+    if input.is_empty() || input.len() > MAX_BASE58_INPUT {
+        return None;
+    }
+
+    // Little-endian accumulator for the decoded big integer
+    let mut buf = [0u8; BASE58_BUFFER];
+    let mut len = 0;
+
+    for c in input.iter() {
+        let mut carry = base58_value(c)? as u32;
+        for byte in buf[..len].iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            buf[len] = carry as u8;
+            len += 1;
+            carry >>= 8;
+        }
+    }
+
+    let mut out = Bytes::new(env);
+    for _ in input.iter().take_while(|&c| c == b'1') {
+        out.push_back(0);
+    }
+    for i in (0..len).rev() {
+        out.push_back(buf[i]);
+    }
+
+    Some(out)
+}
+
+/// Decode standard-alphabet base64 into raw bytes
+///
+/// Padding is optional; when present it must complete a multiple of four
+/// characters. Returns `None` for empty input, a dangling single character
+/// or any character outside the alphabet.
+pub fn base64_decode(env: &Env, input: &Bytes) -> Option<Bytes> {
+    let mut len = input.len();
+    if len % 4 == 0 {
+        for _ in 0..2 {
+            if len > 0 && input.get_unchecked(len - 1) == b'=' {
+                len -= 1;
+            }
+        }
+    }
+    if len == 0 || len % 4 == 1 {
+        return None;
+    }
+
+    let mut out = Bytes::new(env);
+    let mut acc = 0u32;
+    let mut bits = 0;
+    for c in input.slice(0..len).iter() {
+        acc = ((acc << 6) | base64_value(c)? as u32) & 0xfff;
+        bits += 6;
+        if bits >= 8 {
+            bits -= 8;
+            out.push_back((acc >> bits) as u8);
+        }
+    }
+
+    Some(out)
+}
+
+/// Decode a hash submitted as hex or base58 into its raw digest
+///
+/// Hex is tried first, so an input that is valid in both alphabets is read
+/// as hex. Failure maps to `HashVerificationFailed`, the crate's hash format
+/// error. Base64 is not tried here since hex strings are valid base64 too;
+/// callers opt in with `base64_decode`.
+pub fn decode_any(env: &Env, input: &Bytes) -> Result<Bytes, MetadataError> {
+    decode_hex(env, input)
+        .or_else(|| decode_base58(env, input))
+        .ok_or(MetadataError::HashVerificationFailed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIGEST: [u8; 32] = [
+        212, 240, 188, 90, 41, 222, 6, 181, 16, 249, 170, 66, 143, 30, 237, 186, 146, 96, 18, 181,
+        145, 254, 247, 165, 24, 231, 118, 167, 201, 189, 24, 36,
+    ];
+
+    #[test]
+    fn test_decode_any_hex_and_base58_agree() {
+        let env = Env::default();
+        let expected = Bytes::from_array(&env, &DIGEST);
+
+        let hex = Bytes::from_slice(
+            &env,
+            b"d4f0bc5a29de06b510f9aa428f1eedba926012b591fef7a518e776a7c9bd1824"
+        );
+        let base58 = Bytes::from_slice(&env, b"FLELiEbZiFHjRSx9uZssckQd8RtPu3nLCKFB1gTuXnNX");
+
+        assert_eq!(decode_any(&env, &hex), Ok(expected.clone()));
+        assert_eq!(decode_any(&env, &base58), Ok(expected));
+    }
+
+    #[test]
+    fn test_decode_base58_leading_zeros() {
+        let env = Env::default();
+
+        let decoded = decode_base58(&env, &Bytes::from_slice(&env, b"11z")).unwrap();
+        assert_eq!(decoded, Bytes::from_slice(&env, &[0, 0, 57]));
+    }
+
+    #[test]
+    fn test_decode_any_rejects_invalid() {
+        let env = Env::default();
+
+        assert!(decode_any(&env, &Bytes::from_slice(&env, b"")).is_err());
+        assert!(decode_any(&env, &Bytes::from_slice(&env, b"0OIl")).is_err());
+    }
+
+    #[test]
+    fn test_base64_decode_padded_and_unpadded() {
+        let env = Env::default();
+        let expected = Bytes::from_array(&env, &DIGEST);
+
+        let padded = Bytes::from_slice(&env, b"1PC8WineBrUQ+apCjx7tupJgErWR/velGOd2p8m9GCQ=");
+        let unpadded = Bytes::from_slice(&env, b"1PC8WineBrUQ+apCjx7tupJgErWR/velGOd2p8m9GCQ");
+
+        assert_eq!(base64_decode(&env, &padded), Some(expected.clone()));
+        assert_eq!(base64_decode(&env, &unpadded), Some(expected));
+        assert_eq!(base64_decode(&env, &Bytes::from_slice(&env, b"1PC8-ine")), None);
+        assert_eq!(base64_decode(&env, &Bytes::from_slice(&env, b"QQ=A")), None);
+        assert_eq!(base64_decode(&env, &Bytes::from_slice(&env, b"Q")), None);
+    }
+
+    #[test]
+    fn test_env_free_validate() {
+        assert!(validate(b"a1b2c3d4e5f6789012345678901234567890abcdef").is_ok());
+        assert_eq!(validate(b"abc"), Err(MetadataError::HashVerificationFailed));
+        assert_eq!(validate(&[b'a'; 129]), Err(MetadataError::HashVerificationFailed));
+    }
+
+    #[test]
+    fn test_to_raw() {
+        let env = Env::default();
+        let hex = Bytes::from_slice(
+            &env,
+            b"d4f0bc5a29de06b510f9aa428f1eedba926012b591fef7a518e776a7c9bd1824"
+        );
+        let raw = Bytes::from_array(&env, &DIGEST);
+
+        assert_eq!(to_raw(&env, &hex), Ok(raw.clone()));
+        assert_eq!(to_raw(&env, &raw), Ok(raw));
+        assert_eq!(
+            to_raw(&env, &Bytes::from_slice(&env, b"not-hex")),
+            Err(MetadataError::HashVerificationFailed)
+        );
+    }
+}
@@ -0,0 +1,151 @@
+//! Read-only detection of how each metadata field is encoded.
+//!
+//! Detection never fails: fields that match no known encoding are reported
+//! as `Unknown`. Intended for diagnostics, not validation.
+
+use soroban_sdk::{ Bytes, Env, Vec };
+
+use crate::{ cid, hash, text, MetadataInput };
+
+/// Detected encoding of a CID
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum CidEncoding {
+    /// Base58btc CIDv0 (`Qm...`)
+    V0,
+    /// CIDv1 in lowercase base32 (`b...`)
+    V1Base32Lower,
+    /// CIDv1 in uppercase base32 (`B...`)
+    V1Base32Upper,
+    /// CIDv1 in base58btc (`z...`)
+    V1Base58,
+    /// Another multibase prefix (`f`/`F`, `k`, `m`/`u`); the body is not decoded
+    V1Other,
+    Unknown,
+}
+
+/// Detected encoding of a model hash
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HashEncoding {
+    Hex,
+    Base58,
+    Unknown,
+}
+
+/// Detected encoding of a text field
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TextEncoding {
+    /// Printable ASCII only
+    Ascii,
+    /// Well-formed UTF-8 with non-ASCII characters
+    Utf8,
+    Unknown,
+}
+
+/// Detected encoding of every scalar field
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FieldEncodings {
+    pub json_cid: CidEncoding,
+    pub model_hash: HashEncoding,
+    pub name: TextEncoding,
+    pub description: TextEncoding,
+    pub version: TextEncoding,
+}
+
+/// Counts of CID versions and hash encodings across a batch
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct EncodingReport {
+    /// Items with a CIDv0
+    pub cid_v0: u32,
+    /// Items with a CIDv1 in any multibase
+    pub cid_v1: u32,
+    /// Items whose CID matched no known encoding
+    pub cid_unknown: u32,
+    /// Items with a hex model hash
+    pub hash_hex: u32,
+    /// Items with a base58 model hash
+    pub hash_base58: u32,
+    /// Items whose model hash matched no known encoding
+    pub hash_unknown: u32,
+}
+
+impl EncodingReport {
+    /// Whether every item used the same CID version and hash encoding
+    pub fn is_consistent(&self) -> bool {
+        let cid_kinds = [self.cid_v0, self.cid_v1, self.cid_unknown];
+        let hash_kinds = [self.hash_hex, self.hash_base58, self.hash_unknown];
+        cid_kinds.iter().filter(|&&n| n > 0).count() <= 1 &&
+            hash_kinds.iter().filter(|&&n| n > 0).count() <= 1
+    }
+}
+
+/// Tally the CID and hash encodings of every item
+pub fn report(env: &Env, items: &Vec<MetadataInput>) -> EncodingReport {
+    let mut report = EncodingReport::default();
+    for item in items.iter() {
+        let counter = match detect_cid(env, &item.json_cid) {
+            CidEncoding::V0 => &mut report.cid_v0,
+            CidEncoding::Unknown => &mut report.cid_unknown,
+            _ => &mut report.cid_v1,
+        };
+        *counter = counter.saturating_add(1);
+
+        let counter = match detect_hash(env, &item.model_hash) {
+            HashEncoding::Hex => &mut report.hash_hex,
+            HashEncoding::Base58 => &mut report.hash_base58,
+            HashEncoding::Unknown => &mut report.hash_unknown,
+        };
+        *counter = counter.saturating_add(1);
+    }
+    report
+}
+
+/// Detect the encoding of a CID
+pub fn detect_cid(env: &Env, value: &Bytes) -> CidEncoding {
+    match cid::version(env, value) {
+        Some(0) => CidEncoding::V0,
+        Some(1) =>
+            match value.first() {
+                Some(b'b') => CidEncoding::V1Base32Lower,
+                Some(b'B') => CidEncoding::V1Base32Upper,
+                _ => CidEncoding::V1Base58,
+            }
+        _ =>
+            match value.first() {
+                Some(b'f' | b'F' | b'k' | b'm' | b'u') => CidEncoding::V1Other,
+                _ => CidEncoding::Unknown,
+            }
+    }
+}
+
+/// Detect the encoding of a model hash; hex wins when both would decode
+pub fn detect_hash(env: &Env, value: &Bytes) -> HashEncoding {
+    if hash::decode_hex(env, value).is_some() {
+        HashEncoding::Hex
+    } else if hash::decode_base58(env, value).is_some() {
+        HashEncoding::Base58
+    } else {
+        HashEncoding::Unknown
+    }
+}
+
+/// Detect the encoding of a text field
+pub fn detect_text(value: &Bytes) -> TextEncoding {
+    if !text::is_printable(value) || !text::is_valid_utf8(value) {
+        TextEncoding::Unknown
+    } else if value.iter().all(|b| b.is_ascii()) {
+        TextEncoding::Ascii
+    } else {
+        TextEncoding::Utf8
+    }
+}
+
+/// Detect the encoding of every scalar field in `input`
+pub fn detect(env: &Env, input: &MetadataInput) -> FieldEncodings {
+    FieldEncodings {
+        json_cid: detect_cid(env, &input.json_cid),
+        model_hash: detect_hash(env, &input.model_hash),
+        name: detect_text(&input.name),
+        description: detect_text(&input.description),
+        version: detect_text(&input.version),
+    }
+}
@@ -0,0 +1,52 @@
+//! Minimal JSON emission for metadata export.
+//!
+//! Only what the canonical export needs: string escaping into a `Bytes`
+//! buffer. Non-ASCII bytes are copied through unchanged.
+
+use soroban_sdk::Bytes;
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Append `value` to `out` as a quoted, escaped JSON string
+pub fn push_string(out: &mut Bytes, value: &Bytes) {
+    out.push_back(b'"');
+    for b in value.iter() {
+        match b {
+            b'"' => out.extend_from_slice(b"\\\""),
+            b'\\' => out.extend_from_slice(b"\\\\"),
+            b'\n' => out.extend_from_slice(b"\\n"),
+            b'\r' => out.extend_from_slice(b"\\r"),
+            b'\t' => out.extend_from_slice(b"\\t"),
+            0x00..=0x1F => {
+                out.extend_from_slice(b"\\u00");
+                out.push_back(HEX_DIGITS[(b >> 4) as usize]);
+                out.push_back(HEX_DIGITS[(b & 0x0F) as usize]);
+            }
+            _ => out.push_back(b),
+        }
+    }
+    out.push_back(b'"');
+}
+
+/// Append `"key":` to `out`
+pub fn push_key(out: &mut Bytes, key: &[u8]) {
+    out.push_back(b'"');
+    out.extend_from_slice(key);
+    out.extend_from_slice(b"\":");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::Env;
+
+    #[test]
+    fn test_push_string_escapes() {
+        let env = Env::default();
+        let mut out = Bytes::new(&env);
+
+        push_string(&mut out, &Bytes::from_slice(&env, b"a\"b\\c\nd\x01"));
+
+        assert_eq!(out, Bytes::from_slice(&env, b"\"a\\\"b\\\\c\\nd\\u0001\""));
+    }
+}
@@ -1,6 +1,8 @@
 #![no_std]
 
-use soroban_sdk::{ contracttype, Bytes, Env, Vec, Address };
+use core::cell::RefCell;
+
+use soroban_sdk::{ contracttype, Bytes, Env, Vec, Address, Symbol };
 use common_utils::error::{ ValidationError, ContractError };
 use common_utils::validator::{
     Validator,
@@ -10,6 +12,30 @@ use common_utils::validator::{
     ValidatorConfig,
 };
 
+use cache::CidCache;
+
+pub mod builder;
+pub mod cache;
+pub mod cid;
+pub mod config;
+pub mod encoding;
+pub mod hash;
+pub mod inspect;
+pub mod json;
+pub mod schema;
+pub mod storage;
+pub mod text;
+mod util;
+pub mod version;
+pub mod well_known;
+
+pub use builder::MetadataBuilder;
+pub use config::{ CasePolicy, ParserConfig, VersionScheme };
+pub use hash::HashAlgo;
+pub use inspect::{ EncodingReport, FieldEncodings };
+pub use schema::{ Schema, ValueType };
+pub use storage::MetadataKey;
+
 /// Legacy error type for backward compatibility
 /// Maps to new ValidationError codes
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
@@ -29,9 +55,51 @@ pub enum MetadataError {
     CidTooLong = 6,
     /// Hash too long -> ValidationError::InvalidLength
     HashTooLong = 7,
+    /// Field or collection exceeds a configured limit -> ValidationError::InvalidLength
+    InvalidLength = 8,
+    /// Same extra field key carries conflicting values -> ValidationError::InvalidFormat
+    DuplicateExtraField = 9,
+    /// Name already registered to a different agent -> ValidationError::InvalidFormat
+    NameTaken = 10,
+    /// Extra field key is not permitted here -> ValidationError::InvalidFormat
+    DisallowedExtraField = 11,
 }
 
 impl MetadataError {
+    /// Stable numeric code for API clients
+    ///
+    /// Codes are frozen and independent of the enum discriminants; new
+    /// variants get the next unused code and existing codes never change.
+    ///
+    /// | code | variant                  |
+    /// |------|--------------------------|
+    /// | 2001 | `InvalidJsonFormat`      |
+    /// | 2002 | `MissingRequiredField`   |
+    /// | 2003 | `InvalidCidFormat`       |
+    /// | 2004 | `HashVerificationFailed` |
+    /// | 2005 | `InvalidStructure`       |
+    /// | 2006 | `CidTooLong`             |
+    /// | 2007 | `HashTooLong`            |
+    /// | 2008 | `InvalidLength`          |
+    /// | 2009 | `DuplicateExtraField`    |
+    /// | 2010 | `NameTaken`              |
+    /// | 2011 | `DisallowedExtraField`   |
+    pub fn api_code(&self) -> u32 {
+        match self {
+            MetadataError::InvalidJsonFormat => 2001,
+            MetadataError::MissingRequiredField => 2002,
+            MetadataError::InvalidCidFormat => 2003,
+            MetadataError::HashVerificationFailed => 2004,
+            MetadataError::InvalidStructure => 2005,
+            MetadataError::CidTooLong => 2006,
+            MetadataError::HashTooLong => 2007,
+            MetadataError::InvalidLength => 2008,
+            MetadataError::DuplicateExtraField => 2009,
+            MetadataError::NameTaken => 2010,
+            MetadataError::DisallowedExtraField => 2011,
+        }
+    }
+
     /// Convert legacy MetadataError to new ValidationError
     pub fn to_validation_error(&self) -> ValidationError {
         match self {
@@ -39,9 +107,13 @@ impl MetadataError {
             MetadataError::MissingRequiredField => ValidationError::MissingRequiredField,
             MetadataError::InvalidCidFormat => ValidationError::InvalidCidFormat,
             MetadataError::HashVerificationFailed => ValidationError::InvalidHashFormat,
-            MetadataError::InvalidStructure => ValidationError::InvalidFormat,
-            MetadataError::CidTooLong | MetadataError::HashTooLong =>
-                ValidationError::InvalidLength,
+            MetadataError::InvalidStructure |
+            MetadataError::DuplicateExtraField |
+            MetadataError::NameTaken |
+            MetadataError::DisallowedExtraField => ValidationError::InvalidFormat,
+            MetadataError::CidTooLong |
+            MetadataError::HashTooLong |
+            MetadataError::InvalidLength => ValidationError::InvalidLength,
         }
     }
 }
@@ -64,6 +136,664 @@ pub struct AgentMetadata {
     pub extra_fields: Vec<(Bytes, Bytes)>,
 }
 
+/// Unvalidated metadata components as submitted
+///
+/// Mirrors the arguments of `MetadataValidator::validate_and_parse` so a
+/// submission can be passed around as one value before validation.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct MetadataInput {
+    pub json_cid: Bytes,
+    pub model_hash: Bytes,
+    pub name: Bytes,
+    pub description: Bytes,
+    pub version: Bytes,
+    pub extra_fields: Vec<(Bytes, Bytes)>,
+}
+
+impl From<AgentMetadata> for MetadataInput {
+    fn from(metadata: AgentMetadata) -> Self {
+        Self {
+            json_cid: metadata.json_cid,
+            model_hash: metadata.model_hash,
+            name: metadata.name,
+            description: metadata.description,
+            version: metadata.version,
+            extra_fields: metadata.extra_fields,
+        }
+    }
+}
+
+/// A single extra field change between two metadata versions
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum ExtraChange {
+    /// Key present only in the newer metadata: (key, value)
+    Added(Bytes, Bytes),
+    /// Key present only in the older metadata: (key, value)
+    Removed(Bytes, Bytes),
+    /// Key present in both with differing values: (key, old, new)
+    Changed(Bytes, Bytes, Bytes),
+}
+
+/// How `AgentMetadata::merge_extra` resolves a key present in both inputs
+/// with differing values
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum MergePolicy {
+    /// Keep the value from `self`
+    PreferSelf,
+    /// Take the value from `other`
+    PreferOther,
+    /// Fail with `MetadataError::DuplicateExtraField`
+    FailOnConflict,
+}
+
+impl AgentMetadata {
+    /// Layout version written as the first byte of `to_bytes`
+    pub const ENCODING_VERSION: u8 = 1;
+
+    /// Look up an extra field value by key
+    ///
+    /// Returns the first matching entry.
+    pub fn get_extra(&self, key: &Bytes) -> Option<Bytes> {
+        self.extra_fields
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v)
+    }
+
+    /// Look up an extra field value by key, falling back to `default`
+    pub fn get_extra_or(&self, _env: &Env, key: &Bytes, default: Bytes) -> Bytes {
+        self.get_extra(key).unwrap_or(default)
+    }
+
+    /// CIDs listed in the comma-separated `assets` extra field
+    ///
+    /// Returns an empty vec when the field is absent.
+    pub fn asset_cids(&self, env: &Env) -> Vec<Bytes> {
+        match self.get_extra(&Bytes::from_slice(env, well_known::ASSETS)) {
+            Some(assets) => text::split(env, &assets, b','),
+            None => Vec::new(env),
+        }
+    }
+
+    /// Maintainer keys listed in the comma-separated `maintainers` extra field
+    ///
+    /// Entries are returned as submitted (hex text). Returns an empty vec
+    /// when the field is absent.
+    pub fn maintainers(&self, env: &Env) -> Vec<Bytes> {
+        match self.get_extra(&Bytes::from_slice(env, well_known::MAINTAINERS)) {
+            Some(maintainers) => text::split(env, &maintainers, b','),
+            None => Vec::new(env),
+        }
+    }
+
+    /// Distinct tags from the comma-separated `tags` extra field
+    ///
+    /// Repeated tags are collapsed, keeping first-occurrence order. Returns an
+    /// empty vec when the field is absent.
+    pub fn tags(&self, env: &Env) -> Vec<Bytes> {
+        Self::distinct_tags(env, &self.raw_tags(env))
+    }
+
+    fn distinct_tags(env: &Env, raw: &Vec<Bytes>) -> Vec<Bytes> {
+        let mut tags = Vec::new(env);
+        for tag in raw.iter() {
+            if !tags.contains(&tag) {
+                tags.push_back(tag);
+            }
+        }
+        tags
+    }
+
+    fn raw_tags(&self, env: &Env) -> Vec<Bytes> {
+        match self.get_extra(&Bytes::from_slice(env, well_known::TAGS)) {
+            Some(tags) => text::split(env, &tags, b','),
+            None => Vec::new(env),
+        }
+    }
+
+    /// Model size from the `model_size_bytes` extra field
+    ///
+    /// Returns `None` when the field is absent or not a decimal `u64`.
+    pub fn model_size(&self, env: &Env) -> Option<u64> {
+        let value = self.get_extra(&Bytes::from_slice(env, well_known::MODEL_SIZE_BYTES))?;
+        util::parse_u64(&value, true).ok()
+    }
+
+    /// Whether the `deprecated` extra field is exactly `true`
+    ///
+    /// An absent field or any other value means not deprecated.
+    pub fn is_deprecated(&self, env: &Env) -> bool {
+        self.get_extra(&Bytes::from_slice(env, well_known::DEPRECATED)) ==
+            Some(Bytes::from_slice(env, b"true"))
+    }
+
+    /// Rank from the `rank` extra field
+    ///
+    /// Returns `None` when the field is absent or not a decimal `u32`.
+    pub fn rank(&self, env: &Env) -> Option<u32> {
+        let value = self.get_extra(&Bytes::from_slice(env, well_known::RANK))?;
+        u32::try_from(util::parse_u64(&value, true).ok()?).ok()
+    }
+
+    /// CID of the detached signature from the `sig_cid` extra field
+    pub fn sig_cid(&self, env: &Env) -> Option<Bytes> {
+        self.get_extra(&Bytes::from_slice(env, well_known::SIG_CID))
+    }
+
+    /// Stable agent identifier derived from name and version only
+    ///
+    /// Computed as `sha256(name || 0x00 || version)`. The NUL separator keeps
+    /// `("ab", "c")` and `("a", "bc")` apart; it is unambiguous whenever the
+    /// name contains no NUL byte, which `require_printable_text` guarantees.
+    pub fn stable_id(&self, env: &Env) -> Bytes {
+        let mut preimage = self.name.clone();
+        preimage.push_back(0x00);
+        preimage.append(&self.version);
+        Bytes::from_array(env, &env.crypto().sha256(&preimage).to_array())
+    }
+
+    /// Whether `other` matches the name up to surrounding whitespace and ASCII case
+    ///
+    /// A building block for impersonation checks, not a validation rule.
+    pub fn name_matches_loosely(&self, _env: &Env, other: &Bytes) -> bool {
+        text::eq_ignore_ascii_case(&text::trim(&self.name), &text::trim(other))
+    }
+
+    /// Extra fields sorted by key (then value), independent of insertion order
+    pub fn sorted_extra_fields(&self, env: &Env) -> Vec<(Bytes, Bytes)> {
+        let mut sorted: Vec<(Bytes, Bytes)> = Vec::new(env);
+
+        // Insertion sort: extra_fields are small and this avoids allocation
+        // outside the host vector
+        for entry in self.extra_fields.iter() {
+            let mut index = sorted.len();
+            while index > 0 && sorted.get_unchecked(index - 1) > entry {
+                index -= 1;
+            }
+            sorted.insert(index, entry);
+        }
+
+        sorted
+    }
+
+    /// Flatten into key-value pairs
+    ///
+    /// The five scalar fields in declaration order under their `well_known`
+    /// keys, followed by the extra fields in insertion order.
+    pub fn to_kv(&self, env: &Env) -> Vec<(Bytes, Bytes)> {
+        let mut kv = Vec::new(env);
+        let scalars = [
+            (well_known::JSON_CID, &self.json_cid),
+            (well_known::MODEL_HASH, &self.model_hash),
+            (well_known::NAME, &self.name),
+            (well_known::DESCRIPTION, &self.description),
+            (well_known::VERSION, &self.version),
+        ];
+        for (key, value) in scalars {
+            kv.push_back((Bytes::from_slice(env, key), value.clone()));
+        }
+        kv.append(&self.extra_fields);
+        kv
+    }
+
+    /// Copy with extra field keys renamed per an old -> new `map`
+    ///
+    /// Unmapped keys are kept as-is and field order is preserved. Fails with
+    /// `DuplicateExtraField` if a renamed key ends up sharing its key with
+    /// any other extra field.
+    pub fn rekey_extra(
+        &self,
+        env: &Env,
+        map: Vec<(Bytes, Bytes)>
+    ) -> Result<AgentMetadata, MetadataError> {
+        let mut renamed = Vec::new(env);
+        let mut extra_fields = Vec::new(env);
+        for (key, value) in self.extra_fields.iter() {
+            let key = match map.iter().find(|(old, _)| *old == key) {
+                Some((_, new)) => {
+                    renamed.push_back(new.clone());
+                    new
+                }
+                None => key,
+            };
+            extra_fields.push_back((key, value));
+        }
+
+        for key in renamed.iter() {
+            if extra_fields.iter().filter(|(k, _)| *k == key).count() > 1 {
+                return Err(MetadataError::DuplicateExtraField);
+            }
+        }
+
+        Ok(AgentMetadata { extra_fields, ..self.clone() })
+    }
+
+    /// Equality ignoring extra field insertion order
+    ///
+    /// Scalar fields must match exactly; extra fields are compared as
+    /// multisets of (key, value) pairs, so repeated pairs must occur equally
+    /// often on both sides.
+    pub fn eq_unordered(&self, env: &Env, other: &AgentMetadata) -> bool {
+        self.json_cid == other.json_cid &&
+            self.model_hash == other.model_hash &&
+            self.name == other.name &&
+            self.description == other.description &&
+            self.version == other.version &&
+            self.extra_fields.len() == other.extra_fields.len() &&
+            self.sorted_extra_fields(env) == other.sorted_extra_fields(env)
+    }
+
+    /// Export as canonical JSON
+    ///
+    /// Top-level keys and the keys of the nested `extra` object are emitted in
+    /// lexicographic order with no insignificant whitespace, so equal metadata
+    /// yields identical bytes regardless of extra field insertion order.
+    pub fn to_canonical_json(&self, env: &Env) -> Bytes {
+        let mut out = Bytes::new(env);
+
+        out.push_back(b'{');
+        json::push_key(&mut out, b"description");
+        json::push_string(&mut out, &self.description);
+
+        out.push_back(b',');
+        json::push_key(&mut out, b"extra");
+        out.push_back(b'{');
+        for (i, (key, value)) in self.sorted_extra_fields(env).iter().enumerate() {
+            if i > 0 {
+                out.push_back(b',');
+            }
+            json::push_string(&mut out, &key);
+            out.push_back(b':');
+            json::push_string(&mut out, &value);
+        }
+        out.push_back(b'}');
+
+        let scalars = [
+            (b"json_cid".as_slice(), &self.json_cid),
+            (b"model_hash".as_slice(), &self.model_hash),
+            (b"name".as_slice(), &self.name),
+            (b"version".as_slice(), &self.version),
+        ];
+        for (key, value) in scalars {
+            out.push_back(b',');
+            json::push_key(&mut out, key);
+            json::push_string(&mut out, value);
+        }
+        out.push_back(b'}');
+
+        out
+    }
+
+    /// Canonical binary encoding
+    ///
+    /// `ENCODING_VERSION`, then the scalar fields in declaration order, each
+    /// length-prefixed, followed by `sorted_extra_fields` as a count and
+    /// length-prefixed pairs. Equal metadata encodes identically regardless of
+    /// extra field insertion order.
+    pub fn to_bytes(&self, env: &Env) -> Bytes {
+        let mut out = Bytes::new(env);
+        out.push_back(Self::ENCODING_VERSION);
+        encoding::push_field(&mut out, &self.json_cid);
+        encoding::push_field(&mut out, &self.model_hash);
+        encoding::push_field(&mut out, &self.name);
+        encoding::push_field(&mut out, &self.description);
+        encoding::push_field(&mut out, &self.version);
+        out.append(&encoding::encode_extra_fields(env, &self.sorted_extra_fields(env)));
+        out
+    }
+
+    /// Decode metadata written by `to_bytes`
+    ///
+    /// An unknown version byte, truncated input or trailing bytes fail with
+    /// `InvalidStructure`. The result is not re-validated.
+    pub fn from_bytes(env: &Env, data: &Bytes) -> Result<AgentMetadata, MetadataError> {
+        let mut reader = encoding::Reader::new(data.clone());
+        if reader.read_u8()? != Self::ENCODING_VERSION {
+            return Err(MetadataError::InvalidStructure);
+        }
+        let metadata = AgentMetadata {
+            json_cid: reader.read_field()?,
+            model_hash: reader.read_field()?,
+            name: reader.read_field()?,
+            description: reader.read_field()?,
+            version: reader.read_field()?,
+            extra_fields: reader.read_extra_fields(env)?,
+        };
+        reader.finish()?;
+        Ok(metadata)
+    }
+
+    /// `to_bytes` run-length compressed for storage
+    ///
+    /// Deterministic; pays off on long runs such as the zero bytes of length
+    /// prefixes and repetitive values, and never grows the input by more than
+    /// one byte per 128.
+    pub fn to_compressed_bytes(&self, env: &Env) -> Bytes {
+        encoding::rle_compress(env, &self.to_bytes(env))
+    }
+
+    /// Decode metadata written by `to_compressed_bytes`
+    ///
+    /// Malformed input fails with `InvalidStructure`, as in `from_bytes`.
+    pub fn from_compressed_bytes(
+        env: &Env,
+        data: &Bytes
+    ) -> Result<AgentMetadata, MetadataError> {
+        AgentMetadata::from_bytes(env, &encoding::rle_decompress(env, data)?)
+    }
+
+    /// Compact encoding of the fields a search index needs
+    ///
+    /// `name`, `version` and `description` length-prefixed as in `to_bytes`,
+    /// then the distinct `tags` as a count and length-prefixed entries. The
+    /// CID, model hash and other extra fields are left out.
+    pub fn search_projection(&self, env: &Env) -> Bytes {
+        let mut out = Bytes::new(env);
+        encoding::push_field(&mut out, &self.name);
+        encoding::push_field(&mut out, &self.version);
+        encoding::push_field(&mut out, &self.description);
+
+        let tags = self.tags(env);
+        out.extend_from_slice(&tags.len().to_be_bytes());
+        for tag in tags.iter() {
+            encoding::push_field(&mut out, &tag);
+        }
+        out
+    }
+
+    /// Whether the canonical footprint fits in one storage entry
+    ///
+    /// Compares `storage_footprint` against `storage::MAX_ENTRY_SIZE`. The
+    /// host's XDR encoding adds some per-field overhead, so metadata close to
+    /// the limit may still be rejected at the storage layer.
+    pub fn fits_entry_limit(&self) -> bool {
+        self.storage_footprint() <= storage::MAX_ENTRY_SIZE
+    }
+
+    /// SHA-256 of the canonical encoding from `to_bytes`
+    ///
+    /// Covers every field, independent of extra field insertion order.
+    pub fn fingerprint(&self, env: &Env) -> Bytes {
+        Bytes::from_array(env, &env.crypto().sha256(&self.to_bytes(env)).to_array())
+    }
+
+    /// Deterministic seed for rendering an identicon
+    ///
+    /// The first 8 bytes of `fingerprint` as a big-endian `u64`.
+    pub fn identicon_seed(&self, env: &Env) -> u64 {
+        let mut seed = [0u8; 8];
+        self.fingerprint(env).slice(0..8).copy_into_slice(&mut seed);
+        u64::from_be_bytes(seed)
+    }
+
+    /// Signed change in `storage_footprint` going from `self` to `other`
+    ///
+    /// Negative when the update shrinks storage.
+    pub fn footprint_delta(&self, other: &AgentMetadata) -> i64 {
+        other.storage_footprint() as i64 - self.storage_footprint() as i64
+    }
+
+    /// Byte size of the canonical encoding, a proxy for storage rent
+    ///
+    /// Computed arithmetically, without materialising `to_bytes`.
+    pub fn storage_footprint(&self) -> u32 {
+        let mut size = encoding::VERSION_SIZE
+            .saturating_add(encoding::field_size(&self.json_cid))
+            .saturating_add(encoding::field_size(&self.model_hash))
+            .saturating_add(encoding::field_size(&self.name))
+            .saturating_add(encoding::field_size(&self.description))
+            .saturating_add(encoding::field_size(&self.version))
+            .saturating_add(encoding::PREFIX_SIZE);
+
+        for (key, value) in self.extra_fields.iter() {
+            size = size
+                .saturating_add(encoding::field_size(&key))
+                .saturating_add(encoding::field_size(&value));
+        }
+
+        size
+    }
+
+    /// Fingerprint of the extra fields alone
+    ///
+    /// SHA-256 over the length-prefixed encoding of `sorted_extra_fields`, so
+    /// it changes only when an extra field changes, independent of insertion
+    /// order and of the scalar fields.
+    pub fn extra_fingerprint(&self, env: &Env) -> Bytes {
+        let encoded = encoding::encode_extra_fields(env, &self.sorted_extra_fields(env));
+        Bytes::from_array(env, &env.crypto().sha256(&encoded).to_array())
+    }
+
+    /// Merge `other`'s extra fields into a copy of `self`
+    ///
+    /// Scalar fields come from `self`. Keys only in `other` are appended in
+    /// `other`'s order; overlapping keys with equal values are kept once, and
+    /// differing values are resolved by `policy`.
+    pub fn merge_extra(
+        &self,
+        env: &Env,
+        other: &AgentMetadata,
+        policy: MergePolicy
+    ) -> Result<AgentMetadata, MetadataError> {
+        let mut merged = self.clone();
+        let mut appended = Vec::new(env);
+
+        for (key, value) in other.extra_fields.iter() {
+            let existing = merged.extra_fields
+                .iter()
+                .position(|(k, _)| k == key);
+
+            match existing {
+                None => appended.push_back((key, value)),
+                Some(index) => {
+                    let index = index as u32;
+                    let (_, current) = merged.extra_fields.get_unchecked(index);
+                    if current == value {
+                        continue;
+                    }
+                    match policy {
+                        MergePolicy::PreferSelf => {}
+                        MergePolicy::PreferOther => merged.extra_fields.set(index, (key, value)),
+                        MergePolicy::FailOnConflict => {
+                            return Err(MetadataError::DuplicateExtraField);
+                        }
+                    }
+                }
+            }
+        }
+
+        merged.extra_fields.append(&appended);
+        Ok(merged)
+    }
+
+    /// Produce a changelog of extra fields going from `self` to `other`
+    ///
+    /// Fields are matched by key. Removals and changes are listed in `self`'s
+    /// order, followed by additions in `other`'s order.
+    pub fn extra_field_diff(&self, env: &Env, other: &AgentMetadata) -> Vec<ExtraChange> {
+        let mut changes = Vec::new(env);
+
+        for (key, old) in self.extra_fields.iter() {
+            match other.get_extra(&key) {
+                None => changes.push_back(ExtraChange::Removed(key, old)),
+                Some(new) if new != old => changes.push_back(ExtraChange::Changed(key, old, new)),
+                Some(_) => {}
+            }
+        }
+
+        for (key, new) in other.extra_fields.iter() {
+            if self.get_extra(&key).is_none() {
+                changes.push_back(ExtraChange::Added(key, new));
+            }
+        }
+
+        changes
+    }
+
+    /// Classify the change from `self` to `other` for audit logging
+    ///
+    /// `Material` wins when both material and cosmetic fields differ. Extra
+    /// fields are compared in order, so a pure reordering is `Cosmetic`.
+    pub fn change_class(&self, other: &AgentMetadata) -> ChangeClass {
+        if
+            self.json_cid != other.json_cid ||
+            self.model_hash != other.model_hash ||
+            self.version != other.version ||
+            self.name != other.name
+        {
+            ChangeClass::Material
+        } else if
+            self.description != other.description ||
+            self.extra_fields != other.extra_fields
+        {
+            ChangeClass::Cosmetic
+        } else {
+            ChangeClass::None
+        }
+    }
+
+    /// Compact record of the fields that differ going from `self` to `other`
+    ///
+    /// A 4-byte big-endian bitmask of changed fields (`DIFF_*` bits) followed
+    /// by the new value of each changed scalar, length-prefixed, in declaration
+    /// order. If the extra fields differ they are written whole, in `other`'s
+    /// order. Identical metadata yields a zero mask and nothing else.
+    pub fn binary_diff(&self, env: &Env, other: &AgentMetadata) -> Bytes {
+        let scalars = [
+            (&self.json_cid, &other.json_cid),
+            (&self.model_hash, &other.model_hash),
+            (&self.name, &other.name),
+            (&self.description, &other.description),
+            (&self.version, &other.version),
+        ];
+
+        let mut mask = 0u32;
+        let mut body = Bytes::new(env);
+        for (bit, (old, new)) in scalars.iter().enumerate() {
+            if old != new {
+                mask |= 1 << bit;
+                encoding::push_field(&mut body, new);
+            }
+        }
+        if self.extra_fields != other.extra_fields {
+            mask |= DIFF_EXTRA_FIELDS;
+            body.append(&encoding::encode_extra_fields(env, &other.extra_fields));
+        }
+
+        let mut out = Bytes::from_array(env, &mask.to_be_bytes());
+        out.append(&body);
+        out
+    }
+
+    /// Reconstruct the target metadata from a `binary_diff` against `self`
+    ///
+    /// Unknown mask bits, truncated input or trailing bytes fail with
+    /// `InvalidStructure`. The result is not re-validated.
+    pub fn apply_diff(&self, env: &Env, diff: &Bytes) -> Result<AgentMetadata, MetadataError> {
+        let mut reader = encoding::Reader::new(diff.clone());
+        let mask = reader.read_u32()?;
+        if mask & !DIFF_ALL != 0 {
+            return Err(MetadataError::InvalidStructure);
+        }
+
+        let mut target = self.clone();
+        let scalars = [
+            (DIFF_JSON_CID, &mut target.json_cid),
+            (DIFF_MODEL_HASH, &mut target.model_hash),
+            (DIFF_NAME, &mut target.name),
+            (DIFF_DESCRIPTION, &mut target.description),
+            (DIFF_VERSION, &mut target.version),
+        ];
+        for (bit, field) in scalars {
+            if mask & bit != 0 {
+                *field = reader.read_field()?;
+            }
+        }
+        if mask & DIFF_EXTRA_FIELDS != 0 {
+            target.extra_fields = reader.read_extra_fields(env)?;
+        }
+
+        reader.finish()?;
+        Ok(target)
+    }
+}
+
+/// `binary_diff` mask bit: `json_cid` changed
+pub const DIFF_JSON_CID: u32 = 1 << 0;
+/// `binary_diff` mask bit: `model_hash` changed
+pub const DIFF_MODEL_HASH: u32 = 1 << 1;
+/// `binary_diff` mask bit: `name` changed
+pub const DIFF_NAME: u32 = 1 << 2;
+/// `binary_diff` mask bit: `description` changed
+pub const DIFF_DESCRIPTION: u32 = 1 << 3;
+/// `binary_diff` mask bit: `version` changed
+pub const DIFF_VERSION: u32 = 1 << 4;
+/// `binary_diff` mask bit: `extra_fields` changed
+pub const DIFF_EXTRA_FIELDS: u32 = 1 << 5;
+const DIFF_ALL: u32 = (1 << 6) - 1;
+
+/// Field reported by `MetadataValidator::validate_with_progress`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FieldId {
+    JsonCid,
+    ModelHash,
+    Name,
+    Description,
+    Version,
+    ExtraFields,
+}
+
+/// How much an update changed, as classified by `AgentMetadata::change_class`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ChangeClass {
+    /// Every field is identical
+    None,
+    /// Only presentational fields changed: description or extra fields
+    Cosmetic,
+    /// The CID, model hash, version or name changed
+    Material,
+}
+
+/// Bytes scanned per field during validation
+///
+/// A proxy for the Soroban budget consumed by each validation step, used to
+/// tune limits on large inputs.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct ValidationStats {
+    /// Bytes scanned in the JSON CID
+    pub cid_bytes: u32,
+    /// Bytes scanned in the model hash
+    pub hash_bytes: u32,
+    /// Bytes scanned in the name
+    pub name_bytes: u32,
+    /// Bytes scanned in the description
+    pub description_bytes: u32,
+    /// Bytes scanned in the version
+    pub version_bytes: u32,
+    /// Bytes scanned across all extra field keys and values
+    pub extra_bytes: u32,
+}
+
+impl ValidationStats {
+    /// Total bytes scanned across all fields
+    pub fn total_bytes(&self) -> u32 {
+        self.cid_bytes
+            .saturating_add(self.hash_bytes)
+            .saturating_add(self.name_bytes)
+            .saturating_add(self.description_bytes)
+            .saturating_add(self.version_bytes)
+            .saturating_add(self.extra_bytes)
+    }
+}
+
+/// Deployment-specific rule run after a successful parse
+///
+/// A plain `fn` pointer rather than a closure keeps the validator `no_std`
+/// friendly and free of captured state.
+pub type PostCheck = fn(&Env, &AgentMetadata) -> Result<(), MetadataError>;
+
 /// Main metadata validator and parser using the new validator framework
 pub struct MetadataValidator {
     cid_validator: CIDValidator,
@@ -71,6 +801,9 @@ pub struct MetadataValidator {
     name_validator: BytesValidator,
     description_validator: BytesValidator,
     version_validator: BytesValidator,
+    config: ParserConfig,
+    post_check: Option<PostCheck>,
+    cid_cache: Option<RefCell<CidCache>>,
 }
 
 impl MetadataValidator {
@@ -88,6 +821,9 @@ impl MetadataValidator {
             version_validator: BytesValidator::new().with_config(
                 ValidatorConfig::new().with_length_bounds(1, 50)
             ),
+            config: ParserConfig::default(),
+            post_check: None,
+            cid_cache: None,
         }
     }
 
@@ -105,9 +841,40 @@ impl MetadataValidator {
             name_validator: BytesValidator::with_config(name_config),
             description_validator: BytesValidator::with_config(description_config),
             version_validator: BytesValidator::with_config(version_config),
+            config: ParserConfig::default(),
+            post_check: None,
+            cid_cache: None,
         }
     }
 
+    /// Replace the metadata-level parser configuration
+    pub fn with_parser_config(mut self, config: ParserConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Run `check` on every metadata that passes the built-in validation
+    pub fn with_post_check(mut self, check: PostCheck) -> Self {
+        self.post_check = Some(check);
+        self
+    }
+
+    /// Memoize `validate_cid` results for the lifetime of this validator
+    ///
+    /// Useful when a batch repeats the same CID. At most
+    /// `cache::CID_CACHE_CAPACITY` distinct CIDs are remembered.
+    pub fn with_cid_cache(mut self) -> Self {
+        self.cid_cache = Some(RefCell::new(CidCache::new()));
+        self
+    }
+
+    /// Number of `validate_cid` calls answered from the CID cache
+    ///
+    /// Always zero unless `with_cid_cache` was used.
+    pub fn cid_cache_hits(&self) -> u32 {
+        self.cid_cache.as_ref().map_or(0, |cache| cache.borrow().hits())
+    }
+
     /// Validate and parse agent metadata from raw components
     ///
     /// # Arguments
@@ -132,27 +899,7 @@ impl MetadataValidator {
         version: Bytes,
         extra_fields: Vec<(Bytes, Bytes)>
     ) -> Result<AgentMetadata, MetadataError> {
-        // Validate JSON CID format using new validator
-        self.cid_validator.validate(env, &json_cid).map_err(|_| MetadataError::InvalidCidFormat)?;
-
-        // Validate model hash format using new validator
-        self.hash_validator
-            .validate(env, &model_hash)
-            .map_err(|_| MetadataError::HashVerificationFailed)?;
-
-        // Validate required fields are not empty using new validators
-        self.name_validator.validate(env, &name).map_err(|_| MetadataError::MissingRequiredField)?;
-
-        self.description_validator
-            .validate(env, &description)
-            .map_err(|_| MetadataError::MissingRequiredField)?;
-
-        self.version_validator
-            .validate(env, &version)
-            .map_err(|_| MetadataError::MissingRequiredField)?;
-
-        // Create structured metadata object
-        let metadata = AgentMetadata {
+        let input = MetadataInput {
             json_cid,
             model_hash,
             name,
@@ -160,318 +907,3456 @@ impl MetadataValidator {
             version,
             extra_fields,
         };
-
-        Ok(metadata)
+        self.parse_input(env, input, None)
     }
 
-    /// Validate JSON CID format only
-    pub fn validate_cid(&self, env: &Env, cid: &Bytes) -> Result<(), MetadataError> {
-        self.cid_validator.validate(env, cid).map_err(|_| MetadataError::InvalidCidFormat)
-    }
+    /// Validate a bundled submission, then check its extra fields against
+    /// `schema`
+    ///
+    /// A missing required field fails with `MissingRequiredField`, a value
+    /// of the wrong type with `InvalidStructure`, and an undeclared field,
+    /// unless the schema allows unknown fields, with `DisallowedExtraField`.
+    pub fn validate_against_schema(
+        &self,
+        env: &Env,
+        input: MetadataInput,
+        schema: &Schema
+    ) -> Result<AgentMetadata, MetadataError> {
+        let metadata = self.validate_input(env, input)?;
 
-    /// Validate model hash format only
-    pub fn validate_model_hash(&self, env: &Env, hash: &Bytes) -> Result<(), MetadataError> {
-        self.hash_validator.validate(env, hash).map_err(|_| MetadataError::HashVerificationFailed)
+        for (key, _, required) in schema.fields.iter() {
+            if required && metadata.get_extra(&key).is_none() {
+                return Err(MetadataError::MissingRequiredField);
+            }
+        }
+
+        for (key, value) in metadata.extra_fields.iter() {
+            let well_typed = match schema.value_type(&key) {
+                Some(ValueType::Text) => text::is_printable(&value) && text::is_valid_utf8(&value),
+                Some(ValueType::Integer) => util::parse_u64(&value, true).is_ok(),
+                Some(ValueType::Cid) => self.validate_cid(env, &value).is_ok(),
+                Some(ValueType::Hash) => self.validate_model_hash(env, &value).is_ok(),
+                None if schema.allow_unknown => true,
+                None => {
+                    return Err(MetadataError::DisallowedExtraField);
+                }
+            };
+            if !well_typed {
+                return Err(MetadataError::InvalidStructure);
+            }
+        }
+
+        Ok(metadata)
     }
 
-    /// Verify that a provided hash matches the expected hash
-    pub fn verify_hash(
+    /// Validate a bundled submission with protocol-gated rules
+    ///
+    /// Same as `validate_input`, except that once the ledger protocol version
+    /// reaches `strict_cid_protocol_version` the CID must also be CIDv1,
+    /// failing with `InvalidCidFormat`. Lets stricter rules phase in with a
+    /// protocol upgrade.
+    pub fn validate_for_ledger(
         &self,
-        provided_hash: &Bytes,
-        expected_hash: &Bytes
-    ) -> Result<(), MetadataError> {
-        if provided_hash == expected_hash {
-            Ok(())
-        } else {
-            Err(MetadataError::HashVerificationFailed)
+        env: &Env,
+        input: MetadataInput
+    ) -> Result<AgentMetadata, MetadataError> {
+        let metadata = self.validate_input(env, input)?;
+
+        if
+            env.ledger().protocol_version() >= self.config.strict_cid_protocol_version &&
+            cid::version(env, &metadata.json_cid) != Some(1)
+        {
+            return Err(MetadataError::InvalidCidFormat);
         }
-    }
 
-    /// Get the CID validator for external use
-    pub fn cid_validator(&self) -> &CIDValidator {
-        &self.cid_validator
+        Ok(metadata)
     }
 
-    /// Get the hash validator for external use
-    pub fn hash_validator(&self) -> &HashValidator {
-        &self.hash_validator
+    /// Validate a bundled submission, calling `on_field` after each field passes
+    ///
+    /// Fields are reported in validation order: `JsonCid`, `ModelHash`,
+    /// `Name`, `Description`, `Version`, then `ExtraFields` once all extra
+    /// field rules pass. A failing field stops validation without a callback.
+    pub fn validate_with_progress(
+        &self,
+        env: &Env,
+        input: MetadataInput,
+        on_field: fn(FieldId)
+    ) -> Result<AgentMetadata, MetadataError> {
+        self.parse_input(env, input, Some(on_field))
     }
 
-    /// Get the name validator for external use
-    pub fn name_validator(&self) -> &BytesValidator {
-        &self.name_validator
+    fn parse_input(
+        &self,
+        env: &Env,
+        input: MetadataInput,
+        on_field: Option<fn(FieldId)>
+    ) -> Result<AgentMetadata, MetadataError> {
+        let notify = |field| {
+            if let Some(on_field) = on_field {
+                on_field(field);
+            }
+        };
+        let MetadataInput { json_cid, model_hash, name, description, version, extra_fields } =
+            input;
+
+        // Every extra field must decode as a (key, value) pair of bytes;
+        // host-supplied vectors are only type-checked lazily on access
+        for entry in extra_fields.try_iter() {
+            entry.map_err(|_| MetadataError::InvalidStructure)?;
+        }
+
+        // Validate JSON CID format using new validator
+        self.validate_cid(env, &json_cid)?;
+        notify(FieldId::JsonCid);
+
+        // Validate model hash format using new validator
+        self.validate_model_hash(env, &model_hash)?;
+        notify(FieldId::ModelHash);
+
+        // Validate required fields are not empty using new validators
+        self.name_validator.validate(env, &name).map_err(|_| MetadataError::MissingRequiredField)?;
+        if name.len() > self.config.max_name_length {
+            return Err(MetadataError::InvalidLength);
+        }
+        if self.config.reject_blank_text && text::is_blank(&name) {
+            return Err(MetadataError::MissingRequiredField);
+        }
+        if self.config.require_printable_text && !text::is_printable(&name) {
+            return Err(MetadataError::InvalidStructure);
+        }
+        if self.config.name_must_be_dns_label && !text::is_valid_dns_label(&name) {
+            return Err(MetadataError::InvalidStructure);
+        }
+        if self.config.forbid_numeric_name && text::is_all_digits(&name) {
+            return Err(MetadataError::InvalidStructure);
+        }
+        if self.config.ascii_only_name && !name.iter().all(|b| b.is_ascii()) {
+            return Err(MetadataError::InvalidStructure);
+        }
+        notify(FieldId::Name);
+
+        let empty_description_allowed =
+            self.config.allow_empty_description && description.is_empty();
+
+        if !empty_description_allowed {
+            self.description_validator
+                .validate(env, &description)
+                .map_err(|_| MetadataError::MissingRequiredField)?;
+        }
+        if
+            description.len() > self.config.max_description_length ||
+            (self.config.max_description_words != u32::MAX &&
+                text::word_count(&description) > self.config.max_description_words)
+        {
+            return Err(MetadataError::InvalidLength);
+        }
+        if
+            self.config.reject_blank_text &&
+            !empty_description_allowed &&
+            text::is_blank(&description)
+        {
+            return Err(MetadataError::MissingRequiredField);
+        }
+        if self.config.require_printable_text && !text::is_printable(&description) {
+            return Err(MetadataError::InvalidStructure);
+        }
+        if self.config.require_utf8_description && !text::is_valid_utf8(&description) {
+            return Err(MetadataError::InvalidStructure);
+        }
+        if self.config.require_distinct_name_description && name == description {
+            return Err(MetadataError::InvalidStructure);
+        }
+        notify(FieldId::Description);
+
+        self.version_validator
+            .validate(env, &version)
+            .map_err(|_| MetadataError::MissingRequiredField)?;
+        if self.config.forbid_prerelease && version::is_prerelease(&version) {
+            return Err(MetadataError::InvalidStructure);
+        }
+        let follows_scheme = match self.config.version_scheme {
+            VersionScheme::SemVer => version::is_valid_semver(&version),
+            VersionScheme::CalVer => version::is_valid_calver(&version),
+            VersionScheme::Any => true,
+        };
+        if !follows_scheme {
+            return Err(MetadataError::InvalidStructure);
+        }
+        if self.config.forbid_zero_version && version::is_zero(&version) {
+            return Err(MetadataError::InvalidStructure);
+        }
+        notify(FieldId::Version);
+
+        // Create structured metadata object
+        let mut metadata = AgentMetadata {
+            json_cid,
+            model_hash,
+            name,
+            description,
+            version,
+            extra_fields,
+        };
+
+        self.validate_extra_fields(env, &metadata)?;
+        notify(FieldId::ExtraFields);
+
+        if self.config.deep_scan {
+            self.deep_scan(env, &metadata)?;
+        }
+
+        // After the charset checks, which expect the submitted text form
+        if self.config.store_raw_hash {
+            metadata.model_hash = hash::to_raw(env, &metadata.model_hash)?;
+        }
+
+        if let Some(check) = self.post_check {
+            check(env, &metadata)?;
+        }
+
+        Ok(metadata)
     }
-}
 
-impl Default for MetadataValidator {
-    fn default() -> Self {
-        Self::new()
+    /// Byte-by-byte charset checks over every field
+    ///
+    /// The CID must be ASCII alphanumeric (base58 for CIDv0), the hash must
+    /// decode as hex (or base58/base64 when accepted), and all text, extra keys and
+    /// extra values must pass the printable and safe-key checks.
+    fn deep_scan(&self, env: &Env, metadata: &AgentMetadata) -> Result<(), MetadataError> {
+        let cid_charset_ok = match cid::version(env, &metadata.json_cid) {
+            Some(0) => metadata.json_cid.iter().all(hash::is_base58_char),
+            _ => metadata.json_cid.iter().all(|b| b.is_ascii_alphanumeric()),
+        };
+        if !cid_charset_ok {
+            return Err(MetadataError::InvalidCidFormat);
+        }
+
+        let hash_decodes =
+            hash::decode_hex(env, &metadata.model_hash).is_some() ||
+            (self.config.accept_base58_hash &&
+                hash::decode_base58(env, &metadata.model_hash).is_some()) ||
+            (self.config.accept_base64_hash &&
+                hash::base64_decode(env, &metadata.model_hash).is_some());
+        if !hash_decodes {
+            return Err(MetadataError::HashVerificationFailed);
+        }
+
+        let text_ok =
+            text::is_printable(&metadata.name) &&
+            text::is_printable(&metadata.description) &&
+            text::is_printable(&metadata.version);
+        if !text_ok {
+            return Err(MetadataError::InvalidStructure);
+        }
+
+        for (key, value) in metadata.extra_fields.iter() {
+            if !text::is_safe_key(&key) || !text::is_printable(&value) {
+                return Err(MetadataError::InvalidStructure);
+            }
+        }
+
+        Ok(())
     }
-}
 
-/// Convenience functions for common validation operations
-pub mod convenience {
-    use super::*;
+    /// Apply the per-entry extra field checks that need no other fields
+    fn validate_extra_entry(&self, key: &Bytes, value: &Bytes) -> Result<(), MetadataError> {
+        if key.len() > self.config.max_extra_key_length {
+            return Err(MetadataError::InvalidLength);
+        }
+        if self.config.forbid_reserved_keys && well_known::is_reserved_key(key) {
+            return Err(MetadataError::InvalidStructure);
+        }
+        if !self.is_allowed_extra_key(key) {
+            return Err(MetadataError::DisallowedExtraField);
+        }
+        if self.config.forbid_scalar_key_shadowing && well_known::is_scalar_key(key) {
+            return Err(MetadataError::DisallowedExtraField);
+        }
+        if self.config.forbid_empty_extra_values && value.is_empty() {
+            return Err(MetadataError::MissingRequiredField);
+        }
+        if
+            self.config.require_printable_text &&
+            (!text::is_safe_key(key) || !text::is_printable(value))
+        {
+            return Err(MetadataError::InvalidStructure);
+        }
 
-    /// Quick validation of all metadata components
-    pub fn validate_metadata_quick(
+        Ok(())
+    }
+
+    fn is_allowed_extra_key(&self, key: &Bytes) -> bool {
+        match &self.config.allowed_extra_keys {
+            Some(allowed) => allowed.contains(key),
+            None => true,
+        }
+    }
+
+    /// Validate one page of a large extra_fields set
+    ///
+    /// Applies the per-entry checks to `fields[start..start + len]`, returning
+    /// `InvalidLength` if the page runs past the end. Validating every page
+    /// across invocations is equivalent to the per-entry part of
+    /// `validate_and_parse`; aggregate limits (`max_extra_fields`,
+    /// `max_extra_bytes`) and duplicate key detection across pages are the
+    /// caller's responsibility to accumulate.
+    pub fn validate_extra_page(
+        &self,
+        _env: &Env,
+        fields: Vec<(Bytes, Bytes)>,
+        start: u32,
+        len: u32
+    ) -> Result<(), MetadataError> {
+        let end = start.checked_add(len).ok_or(MetadataError::InvalidLength)?;
+        if end > fields.len() {
+            return Err(MetadataError::InvalidLength);
+        }
+
+        for (key, value) in fields.slice(start..end).iter() {
+            self.validate_extra_entry(&key, &value)?;
+        }
+
+        Ok(())
+    }
+
+    /// Apply the configured checks for well-known extra fields
+    fn validate_extra_fields(
+        &self,
+        env: &Env,
+        metadata: &AgentMetadata
+    ) -> Result<(), MetadataError> {
+        if self.config.require_extra_fields && metadata.extra_fields.is_empty() {
+            return Err(MetadataError::MissingRequiredField);
+        }
+
+        if metadata.extra_fields.len() > self.config.max_extra_fields {
+            return Err(MetadataError::InvalidLength);
+        }
+
+        if self.config.require_sorted_extra {
+            let mut previous: Option<Bytes> = None;
+            for (key, _) in metadata.extra_fields.iter() {
+                if previous.is_some_and(|previous| previous >= key) {
+                    return Err(MetadataError::InvalidStructure);
+                }
+                previous = Some(key);
+            }
+        }
+
+        let mut extra_bytes: u32 = 0;
+        for (key, value) in metadata.extra_fields.iter() {
+            self.validate_extra_entry(&key, &value)?;
+            if self.config.type_check_well_known {
+                well_known::validate_value(env, &key, &value)?;
+            }
+            extra_bytes = util::checked_total(&[extra_bytes, key.len(), value.len()])?;
+        }
+        if extra_bytes > self.config.max_extra_bytes {
+            return Err(MetadataError::InvalidLength);
+        }
+
+        if let Some(allowed) = &self.config.allowed_content_types {
+            let key = Bytes::from_slice(env, well_known::CONTENT_TYPE);
+            match metadata.get_extra(&key) {
+                Some(content_type) if allowed.contains(&content_type) => {}
+                _ => return Err(MetadataError::InvalidStructure),
+            }
+        }
+
+        if let Some(allowed) = &self.config.allowed_kinds {
+            let key = Bytes::from_slice(env, well_known::KIND);
+            match metadata.get_extra(&key) {
+                Some(kind) if allowed.contains(&kind) => {}
+                _ => return Err(MetadataError::InvalidStructure),
+            }
+        }
+
+        let assets = metadata.asset_cids(env);
+
+        if self.config.validate_asset_cids {
+            for asset in assets.iter() {
+                self.validate_cid(env, &asset)?;
+            }
+        }
+
+        if
+            self.config.forbid_redundant_asset_cid &&
+            assets.contains(&metadata.json_cid)
+        {
+            return Err(MetadataError::DuplicateExtraField);
+        }
+
+        let sig_cid = metadata.sig_cid(env);
+
+        if self.config.validate_sig_cid {
+            if let Some(sig_cid) = &sig_cid {
+                self.validate_cid(env, sig_cid)?;
+            }
+        }
+
+        if self.config.max_total_cids != u32::MAX {
+            let total_cids = 1 + (sig_cid.is_some() as u32) + assets.len();
+            if total_cids > self.config.max_total_cids {
+                return Err(MetadataError::InvalidLength);
+            }
+        }
+
+        if self.config.validate_maintainers || self.config.max_maintainers != u32::MAX {
+            let maintainers = metadata.maintainers(env);
+            if maintainers.len() > self.config.max_maintainers {
+                return Err(MetadataError::InvalidLength);
+            }
+            if self.config.validate_maintainers {
+                for key in maintainers.iter() {
+                    match hash::decode_hex(env, &key) {
+                        Some(raw) if raw.len() == well_known::MAINTAINER_KEY_LENGTH => {}
+                        _ => return Err(MetadataError::InvalidStructure),
+                    }
+                }
+            }
+        }
+
+        // Collapsing repeats is quadratic in the tag count, so skip it
+        // entirely unless a tag policy is configured
+        if
+            self.config.max_tags != u32::MAX ||
+            self.config.min_distinct_tags > 0 ||
+            self.config.reject_duplicate_tags
+        {
+            let raw_tags = metadata.raw_tags(env);
+            let tags = AgentMetadata::distinct_tags(env, &raw_tags);
+            if tags.len() > self.config.max_tags {
+                return Err(MetadataError::InvalidLength);
+            }
+            if tags.len() < self.config.min_distinct_tags {
+                return Err(MetadataError::MissingRequiredField);
+            }
+            if self.config.reject_duplicate_tags && tags.len() != raw_tags.len() {
+                return Err(MetadataError::InvalidStructure);
+            }
+        }
+
+        if self.config.strict_deprecated {
+            let key = Bytes::from_slice(env, well_known::DEPRECATED);
+            if let Some(value) = metadata.get_extra(&key) {
+                let is_bool = value == Bytes::from_slice(env, b"true") ||
+                    value == Bytes::from_slice(env, b"false");
+                if !is_bool {
+                    return Err(MetadataError::InvalidStructure);
+                }
+            }
+        }
+
+        if self.config.require_spdx_license {
+            let key = Bytes::from_slice(env, well_known::LICENSE);
+            if let Some(license) = metadata.get_extra(&key) {
+                if !well_known::is_valid_spdx(&license) {
+                    return Err(MetadataError::InvalidStructure);
+                }
+            }
+        }
+
+        let allow_leading_zeros = !self.config.reject_leading_zeros;
+
+        if self.config.validate_rank {
+            let key = Bytes::from_slice(env, well_known::RANK);
+            if let Some(value) = metadata.get_extra(&key) {
+                let (min, max) = self.config.rank_range;
+                let rank = util::parse_u64(&value, allow_leading_zeros)?;
+                if rank < u64::from(min) || rank > u64::from(max) {
+                    return Err(MetadataError::InvalidStructure);
+                }
+            }
+        }
+
+        let key = Bytes::from_slice(env, well_known::MODEL_SIZE_BYTES);
+        if let Some(value) = metadata.get_extra(&key) {
+            if self.config.validate_model_size {
+                let size = util::parse_u64(&value, allow_leading_zeros)?;
+                if size == 0 || size > self.config.max_model_size {
+                    return Err(MetadataError::InvalidStructure);
+                }
+            }
+            if
+                self.config.require_assets_with_model_size &&
+                util::parse_u64(&value, true) != Ok(0) &&
+                assets.is_empty()
+            {
+                return Err(MetadataError::InvalidStructure);
+            }
+        }
+
+        if self.config.validate_created_at {
+            let key = Bytes::from_slice(env, well_known::CREATED_AT);
+            if let Some(value) = metadata.get_extra(&key) {
+                let created_at = util::parse_u64(&value, allow_leading_zeros)?;
+                let latest = env
+                    .ledger()
+                    .timestamp()
+                    .saturating_add(self.config.created_at_max_skew);
+                if created_at > latest {
+                    return Err(MetadataError::InvalidStructure);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate and parse a bundled submission
+    pub fn validate_input(
+        &self,
+        env: &Env,
+        input: MetadataInput
+    ) -> Result<AgentMetadata, MetadataError> {
+        self.parse_input(env, input, None)
+    }
+
+    /// Validate a bundled submission and return it with a receipt
+    ///
+    /// The receipt is the 32-byte `AgentMetadata::fingerprint`. The host has
+    /// no secret key to sign with, so the holder of `_signer_key` signs the
+    /// fingerprint off-chain; the signature can later be checked with
+    /// `env.crypto().ed25519_verify`.
+    pub fn validate_and_receipt(
+        &self,
+        env: &Env,
+        input: MetadataInput,
+        _signer_key: &Bytes
+    ) -> Result<(AgentMetadata, Bytes), MetadataError> {
+        let metadata = self.validate_input(env, input)?;
+        let receipt = metadata.fingerprint(env);
+        Ok((metadata, receipt))
+    }
+
+    /// Apply the configured normalizations to validated metadata
+    ///
+    /// Currently only `lowercase_hash`. Normalization never changes whether
+    /// metadata is valid.
+    pub fn normalize(&self, env: &Env, mut metadata: AgentMetadata) -> AgentMetadata {
+        if self.config.lowercase_hash {
+            metadata.model_hash = text::to_ascii_lowercase(env, &metadata.model_hash);
+        }
+        metadata
+    }
+
+    /// Validate and normalize every item of a batch
+    ///
+    /// Stops at the first invalid item and returns its index with the error.
+    pub fn validate_and_normalize_batch(
+        &self,
+        env: &Env,
+        items: Vec<MetadataInput>
+    ) -> Result<Vec<AgentMetadata>, (u32, MetadataError)> {
+        let mut normalized = Vec::new(env);
+        for (index, item) in items.iter().enumerate() {
+            let metadata = self.validate_input(env, item).map_err(|e| (index as u32, e))?;
+            normalized.push_back(self.normalize(env, metadata));
+        }
+        Ok(normalized)
+    }
+
+    /// Re-validate and re-normalize the metadata stored for `agent_id`
+    ///
+    /// Writes the normalized value back only if it differs and returns whether
+    /// it did. Fails with `MissingRequiredField` if nothing is stored, or with
+    /// the validation error if the stored value no longer passes.
+    pub fn migrate(&self, env: &Env, agent_id: &Bytes) -> Result<bool, MetadataError> {
+        let stored = storage::load(env, agent_id).ok_or(MetadataError::MissingRequiredField)?;
+        let validated = self.validate_input(env, stored.clone().into())?;
+        let normalized = self.normalize(env, validated);
+
+        if normalized == stored {
+            return Ok(false);
+        }
+        storage::store(env, agent_id, &normalized);
+        Ok(true)
+    }
+
+    /// Validate `input` with unknown extra fields set aside instead of rejected
+    ///
+    /// Extra fields whose key is outside `allowed_extra_keys` are removed
+    /// before validation and returned alongside the metadata, in input order.
+    /// With no allowlist configured nothing is set aside.
+    pub fn partition_extra(
+        &self,
+        env: &Env,
+        input: MetadataInput
+    ) -> Result<(AgentMetadata, Vec<(Bytes, Bytes)>), MetadataError> {
+        let mut allowed = Vec::new(env);
+        let mut unknown = Vec::new(env);
+        for entry in input.extra_fields.try_iter() {
+            let (key, value) = entry.map_err(|_| MetadataError::InvalidStructure)?;
+            if self.is_allowed_extra_key(&key) {
+                allowed.push_back((key, value));
+            } else {
+                unknown.push_back((key, value));
+            }
+        }
+
+        let metadata = self.validate_input(env, MetadataInput { extra_fields: allowed, ..input })?;
+        Ok((metadata, unknown))
+    }
+
+    /// Report how each field of `input` is encoded, without validating it
+    pub fn inspect(&self, env: &Env, input: &MetadataInput) -> FieldEncodings {
+        inspect::detect(env, input)
+    }
+
+    /// Count CID versions and hash encodings across a batch, without validating
+    ///
+    /// Diagnostic only: a mix of encodings often signals a bad export.
+    pub fn batch_encoding_report(&self, env: &Env, items: Vec<MetadataInput>) -> EncodingReport {
+        inspect::report(env, &items)
+    }
+
+    /// Validate and parse agent metadata, recording bytes scanned per field
+    ///
+    /// Accepts the same arguments as `validate_and_parse`. Sizes are taken
+    /// from the input as submitted, before any normalization such as
+    /// `store_raw_hash`.
+    pub fn validate_and_parse_with_stats(
+        &self,
         env: &Env,
         json_cid: Bytes,
         model_hash: Bytes,
         name: Bytes,
         description: Bytes,
-        version: Bytes
-    ) -> Result<AgentMetadata, MetadataError> {
-        let validator = MetadataValidator::new();
-        let empty_fields = Vec::new(env);
-        validator.validate_and_parse(
+        version: Bytes,
+        extra_fields: Vec<(Bytes, Bytes)>
+    ) -> Result<(AgentMetadata, ValidationStats), MetadataError> {
+        let mut stats = ValidationStats {
+            cid_bytes: json_cid.len(),
+            hash_bytes: model_hash.len(),
+            name_bytes: name.len(),
+            description_bytes: description.len(),
+            version_bytes: version.len(),
+            extra_bytes: 0,
+        };
+
+        for (key, value) in extra_fields.iter() {
+            stats.extra_bytes = util::checked_total(&[stats.extra_bytes, key.len(), value.len()])?;
+        }
+
+        let metadata = self.validate_and_parse(
             env,
             json_cid,
             model_hash,
             name,
             description,
             version,
-            empty_fields
-        )
+            extra_fields
+        )?;
+
+        Ok((metadata, stats))
+    }
+
+    /// Validate JSON CID format only
+    ///
+    /// When `reject_json_blob_cid` is set, a value starting with `{` or `[` is
+    /// rejected outright: that is a JSON document pasted in place of its CID,
+    /// never a CID in any multibase encoding.
+    pub fn validate_cid(&self, env: &Env, cid: &Bytes) -> Result<(), MetadataError> {
+        let cache = match &self.cid_cache {
+            Some(cache) => cache,
+            None => return self.check_cid(env, cid),
+        };
+
+        let key = CidCache::key(env, cid);
+        if let Some(result) = cache.borrow_mut().get(&key) {
+            return result;
+        }
+
+        let result = self.check_cid(env, cid);
+        cache.borrow_mut().insert(key, result);
+        result
+    }
+
+    fn check_cid(&self, env: &Env, cid: &Bytes) -> Result<(), MetadataError> {
+        if self.config.reject_json_blob_cid && cid::is_json_blob_prefix(cid.first()) {
+            return Err(MetadataError::InvalidCidFormat);
+        }
+
+        self.cid_validator.validate(env, cid).map_err(|_| MetadataError::InvalidCidFormat)?;
+
+        if !cid::matches_case_policy(cid, self.config.cidv1_case) {
+            return Err(MetadataError::InvalidCidFormat);
+        }
+
+        if self.config.min_cid_version > 0 {
+            match cid::version(env, cid) {
+                Some(version) if version >= self.config.min_cid_version => {}
+                _ => return Err(MetadataError::InvalidCidFormat),
+            }
+        }
+
+        if self.config.require_cidv1_base32 && !cid::is_valid_cidv1_base32(cid) {
+            return Err(MetadataError::InvalidCidFormat);
+        }
+
+        if let Some(codecs) = &self.config.allowed_cid_codecs {
+            match cid::codec(env, cid) {
+                Some(codec) if codecs.contains(codec) => {}
+                _ => return Err(MetadataError::InvalidCidFormat),
+            }
+        }
+
+        if let Some(prefixes) = &self.config.allowed_cid_prefixes {
+            if !prefixes.is_empty() && !cid::has_any_prefix(cid, prefixes) {
+                return Err(MetadataError::InvalidCidFormat);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Validate model hash format only
+    ///
+    /// With `accept_base58_hash` set, a hash the hash validator rejects is
+    /// still accepted if it decodes as hex or base58; `accept_base64_hash`
+    /// likewise admits standard base64.
+    pub fn validate_model_hash(&self, env: &Env, hash: &Bytes) -> Result<(), MetadataError> {
+        if self.hash_validator.validate(env, hash).is_ok() {
+            return Ok(());
+        }
+
+        let decodes =
+            (self.config.accept_base58_hash && hash::decode_any(env, hash).is_ok()) ||
+            (self.config.accept_base64_hash && hash::base64_decode(env, hash).is_some());
+        if decodes {
+            Ok(())
+        } else {
+            Err(MetadataError::HashVerificationFailed)
+        }
+    }
+
+    /// Validate a model hash and return its raw digest bytes
+    ///
+    /// Hex input is decoded; with `accept_base58_hash` or `accept_base64_hash`
+    /// set, base58 or base64 input is decoded too (in that order). Input in
+    /// no accepted encoding (passed by a lenient hash validator) fails with
+    /// `HashVerificationFailed`, since it carries no digest.
+    pub fn parse_model_hash(&self, env: &Env, hash: &Bytes) -> Result<Bytes, MetadataError> {
+        self.validate_model_hash(env, hash)?;
+
+        if let Some(digest) = hash::decode_hex(env, hash) {
+            return Ok(digest);
+        }
+        if self.config.accept_base58_hash {
+            if let Some(digest) = hash::decode_base58(env, hash) {
+                return Ok(digest);
+            }
+        }
+        if self.config.accept_base64_hash {
+            if let Some(digest) = hash::base64_decode(env, hash) {
+                return Ok(digest);
+            }
+        }
+
+        Err(MetadataError::HashVerificationFailed)
+    }
+
+    /// Hash model bytes delivered as chunks
+    ///
+    /// The host exposes no incremental hasher, so the chunks are appended
+    /// into a single host-side buffer and hashed once; nothing is copied into
+    /// guest memory. The digest equals hashing the concatenation.
+    pub fn compute_hash_chunked(&self, env: &Env, chunks: Vec<Bytes>, algo: HashAlgo) -> Bytes {
+        let mut data = Bytes::new(env);
+        for chunk in chunks.iter() {
+            data.append(&chunk);
+        }
+        hash::digest(env, algo, &data)
+    }
+
+    /// Verify that a provided hash matches the expected hash
+    pub fn verify_hash(
+        &self,
+        provided_hash: &Bytes,
+        expected_hash: &Bytes
+    ) -> Result<(), MetadataError> {
+        if provided_hash == expected_hash {
+            Ok(())
+        } else {
+            Err(MetadataError::HashVerificationFailed)
+        }
+    }
+
+    /// Verify that `cid` embeds `model_hash` as its multihash digest
+    ///
+    /// For self-verifying metadata. The hash is decoded as by
+    /// `parse_model_hash` and compared in constant time against the CID's
+    /// digest. Fails with `InvalidCidFormat` if the CID cannot be decoded and
+    /// with `HashVerificationFailed` on mismatch.
+    pub fn verify_cid_binds_hash(
+        &self,
+        env: &Env,
+        cid: &Bytes,
+        model_hash: &Bytes
+    ) -> Result<(), MetadataError> {
+        let digest = cid::digest(env, cid).ok_or(MetadataError::InvalidCidFormat)?;
+        let expected = self.parse_model_hash(env, model_hash)?;
+        if hash::ct_eq(&digest, &expected) {
+            Ok(())
+        } else {
+            Err(MetadataError::HashVerificationFailed)
+        }
+    }
+
+    /// Validate `input`, store it for `agent_id` and return its fingerprint
+    ///
+    /// Publishes an `agent_registered` event with `agent_id` as the second
+    /// topic and the fingerprint as data. Nothing is stored or published if
+    /// validation fails.
+    pub fn register(
+        &self,
+        env: &Env,
+        agent_id: &Bytes,
+        input: MetadataInput
+    ) -> Result<Bytes, MetadataError> {
+        let metadata = self.validate_input(env, input)?;
+        storage::store(env, agent_id, &metadata);
+
+        let fingerprint = metadata.fingerprint(env);
+        env.events().publish(
+            (Symbol::new(env, "agent_registered"), agent_id.clone()),
+            fingerprint.clone()
+        );
+        Ok(fingerprint)
+    }
+
+    /// Deregister `agent_id`, deleting its metadata and index entry
+    ///
+    /// Fails with `MissingRequiredField` if nothing is stored for the id, so
+    /// a mistyped id is reported rather than silently ignored. A name claimed
+    /// with `register_unique_name` stays claimed.
+    pub fn remove(&self, env: &Env, agent_id: &Bytes) -> Result<(), MetadataError> {
+        if storage::remove(env, agent_id) {
+            Ok(())
+        } else {
+            Err(MetadataError::MissingRequiredField)
+        }
+    }
+
+    /// Ids whose stored metadata no longer matches the expected fingerprint
+    ///
+    /// Takes `(agent_id, expected_fingerprint)` pairs and returns the
+    /// mismatching ids in input order. An id with nothing stored counts as a
+    /// mismatch.
+    pub fn verify_fingerprints(&self, env: &Env, expected: Vec<(Bytes, Bytes)>) -> Vec<Bytes> {
+        let mut drifted = Vec::new(env);
+        for (agent_id, fingerprint) in expected.iter() {
+            let matches = storage::load(env, &agent_id)
+                .is_some_and(|stored| hash::ct_eq(&stored.fingerprint(env), &fingerprint));
+            if !matches {
+                drifted.push_back(agent_id);
+            }
+        }
+        drifted
+    }
+
+    /// Ids of agents with stored metadata
+    ///
+    /// Only the first `storage::MAX_INDEXED_AGENTS` agents stored are listed.
+    pub fn list_agents(env: &Env) -> Vec<Bytes> {
+        storage::list(env)
+    }
+
+    /// Claim `metadata.name` for `agent_id` in the name index
+    ///
+    /// Fails with `NameTaken` if the name is registered to a different id.
+    /// Re-registering a name to its current owner succeeds. Names are matched
+    /// byte for byte.
+    pub fn register_unique_name(
+        &self,
+        env: &Env,
+        agent_id: &Bytes,
+        metadata: &AgentMetadata
+    ) -> Result<(), MetadataError> {
+        match storage::name_owner(env, &metadata.name) {
+            Some(owner) if owner != *agent_id => Err(MetadataError::NameTaken),
+            Some(_) => Ok(()),
+            None => {
+                storage::set_name_owner(env, &metadata.name, agent_id);
+                Ok(())
+            }
+        }
+    }
+
+    /// Get the CID validator for external use
+    pub fn cid_validator(&self) -> &CIDValidator {
+        &self.cid_validator
+    }
+
+    /// Get the hash validator for external use
+    pub fn hash_validator(&self) -> &HashValidator {
+        &self.hash_validator
+    }
+
+    /// Get the name validator for external use
+    pub fn name_validator(&self) -> &BytesValidator {
+        &self.name_validator
+    }
+
+    /// Get the parser configuration
+    pub fn parser_config(&self) -> &ParserConfig {
+        &self.config
+    }
+}
+
+impl Default for MetadataValidator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Convenience functions for common validation operations
+pub mod convenience {
+    use super::*;
+
+    /// Quick validation of all metadata components
+    pub fn validate_metadata_quick(
+        env: &Env,
+        json_cid: Bytes,
+        model_hash: Bytes,
+        name: Bytes,
+        description: Bytes,
+        version: Bytes
+    ) -> Result<AgentMetadata, MetadataError> {
+        let validator = MetadataValidator::new();
+        let empty_fields = Vec::new(env);
+        validator.validate_and_parse(
+            env,
+            json_cid,
+            model_hash,
+            name,
+            description,
+            version,
+            empty_fields
+        )
+    }
+
+    /// Validate a CID and return its canonical CIDv1 base32 form
+    ///
+    /// Fails with `InvalidCidFormat` if the CID is invalid under the default
+    /// validator or cannot be decoded for conversion.
+    pub fn normalize_cid(env: &Env, cid: Bytes) -> Result<Bytes, MetadataError> {
+        MetadataValidator::new().validate_cid(env, &cid)?;
+        cid::to_cidv1_base32(env, &cid).ok_or(MetadataError::InvalidCidFormat)
+    }
+
+    /// Validate only CID and hash (for quick checks)
+    pub fn validate_cid_and_hash(
+        env: &Env,
+        json_cid: Bytes,
+        model_hash: Bytes
+    ) -> Result<(), MetadataError> {
+        let validator = MetadataValidator::new();
+        validator.validate_cid(env, &json_cid)?;
+        validator.validate_model_hash(env, &model_hash)?;
+        Ok(())
+    }
+
+    /// Validate with custom configurations
+    pub fn validate_with_custom_config(
+        env: &Env,
+        json_cid: Bytes,
+        model_hash: Bytes,
+        name: Bytes,
+        description: Bytes,
+        version: Bytes,
+        extra_fields: Vec<(Bytes, Bytes)>,
+        cid_config: ValidatorConfig,
+        hash_config: ValidatorConfig
+    ) -> Result<AgentMetadata, MetadataError> {
+        let validator = MetadataValidator::with_config(
+            cid_config,
+            hash_config,
+            ValidatorConfig::new().with_length_bounds(1, 100),
+            ValidatorConfig::new().with_length_bounds(1, 1000),
+            ValidatorConfig::new().with_length_bounds(1, 50)
+        );
+
+        validator.validate_and_parse(
+            env,
+            json_cid,
+            model_hash,
+            name,
+            description,
+            version,
+            extra_fields
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::{
+        contract,
+        contractimpl,
+        testutils::{ Events, Ledger },
+        Bytes,
+        Env,
+        IntoVal,
+        TryFromVal,
+        Val,
+        Vec,
+    };
+
+    #[contract]
+    pub(crate) struct TestContract;
+
+    #[contractimpl]
+    impl TestContract {}
+
+    pub(crate) fn sample_input(env: &Env) -> MetadataInput {
+        MetadataInput {
+            json_cid: Bytes::from_slice(env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+            model_hash: Bytes::from_slice(env, b"a1b2c3d4e5f6789012345678901234567890abcdef"),
+            name: Bytes::from_slice(env, b"TestAgent"),
+            description: Bytes::from_slice(env, b"A test agent"),
+            version: Bytes::from_slice(env, b"1.0.0"),
+            extra_fields: Vec::new(env),
+        }
+    }
+
+    /// Validate a known-good set of scalar fields with the given extra fields
+    fn parse_with_extras(
+        env: &Env,
+        validator: &MetadataValidator,
+        extras: &[(&[u8], &[u8])]
+    ) -> Result<AgentMetadata, MetadataError> {
+        let mut extra_fields = Vec::new(env);
+        for (key, value) in extras {
+            extra_fields.push_back((Bytes::from_slice(env, key), Bytes::from_slice(env, value)));
+        }
+
+        validator.validate_and_parse(
+            env,
+            Bytes::from_slice(env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+            Bytes::from_slice(env, b"a1b2c3d4e5f6789012345678901234567890abcdef"),
+            Bytes::from_slice(env, b"TestAgent"),
+            Bytes::from_slice(env, b"A test agent"),
+            Bytes::from_slice(env, b"1.0.0"),
+            extra_fields
+        )
+    }
+
+    #[test]
+    fn test_valid_cid_validation() {
+        let env = Env::default();
+        let validator = CIDValidator::new();
+
+        // Test valid CID formats
+        let valid_cid_v0 = Bytes::from_slice(
+            &env,
+            b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"
+        );
+        let valid_cid_v1 = Bytes::from_slice(
+            &env,
+            b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
+        );
+
+        assert!(validator.validate(&env, &valid_cid_v0).is_ok());
+        assert!(validator.validate(&env, &valid_cid_v1).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_cid_validation() {
+        let env = Env::default();
+        let validator = CIDValidator::new();
+
+        // Test invalid CID formats
+        let empty_cid = Bytes::from_slice(&env, b"");
+        let short_cid = Bytes::from_slice(&env, b"Qm");
+        let long_cid = Bytes::from_slice(
+            &env,
+            b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdGQmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdGQmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdGQmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"
+        );
+
+        assert!(validator.validate(&env, &empty_cid).is_err());
+        assert!(validator.validate(&env, &short_cid).is_err());
+        assert!(validator.validate(&env, &long_cid).is_err());
+    }
+
+    #[test]
+    fn test_valid_hash_validation() {
+        let env = Env::default();
+        let validator = HashValidator::new();
+
+        let valid_hash = Bytes::from_slice(&env, b"a1b2c3d4e5f6789012345678901234567890abcdef");
+        let short_valid_hash = Bytes::from_slice(&env, b"00000000000000000000000000000000");
+
+        assert!(validator.validate(&env, &valid_hash).is_ok());
+        assert!(validator.validate(&env, &short_valid_hash).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_hash_validation() {
+        let env = Env::default();
+        let validator = HashValidator::new();
+
+        let empty_hash = Bytes::from_slice(&env, b"");
+        let short_hash = Bytes::from_slice(&env, b"abc");
+        let invalid_chars = Bytes::from_slice(&env, b"xyz123");
+
+        assert!(validator.validate(&env, &empty_hash).is_err());
+        assert!(validator.validate(&env, &short_hash).is_err());
+        // Note: The new validator doesn't check for specific character patterns unless in strict mode
+        assert!(validator.validate(&env, &invalid_chars).is_ok()); // Lenient mode allows this
+    }
+
+    #[test]
+    fn test_complete_metadata_validation() {
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+
+        let json_cid = Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
+        let model_hash = Bytes::from_slice(&env, b"a1b2c3d4e5f6789012345678901234567890abcdef");
+        let name = Bytes::from_slice(&env, b"TestAgent");
+        let description = Bytes::from_slice(&env, b"A test agent for validation");
+        let version = Bytes::from_slice(&env, b"1.0.0");
+        let extra_fields = Vec::new(&env);
+
+        let result = validator.validate_and_parse(
+            &env,
+            json_cid.clone(),
+            model_hash.clone(),
+            name.clone(),
+            description.clone(),
+            version.clone(),
+            extra_fields
+        );
+
+        assert!(result.is_ok());
+        let metadata = result.unwrap();
+        assert_eq!(metadata.json_cid, json_cid);
+        assert_eq!(metadata.model_hash, model_hash);
+        assert_eq!(metadata.name, name);
+        assert_eq!(metadata.description, description);
+        assert_eq!(metadata.version, version);
+    }
+
+    #[test]
+    fn test_missing_required_fields() {
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+
+        let json_cid = Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
+        let model_hash = Bytes::from_slice(&env, b"a1b2c3d4e5f6789012345678901234567890abcdef");
+        let empty_name = Bytes::from_slice(&env, b"");
+        let description = Bytes::from_slice(&env, b"A test agent");
+        let version = Bytes::from_slice(&env, b"1.0.0");
+        let extra_fields = Vec::new(&env);
+
+        let result = validator.validate_and_parse(
+            &env,
+            json_cid,
+            model_hash,
+            empty_name,
+            description,
+            version,
+            extra_fields
+        );
+
+        assert_eq!(result, Err(MetadataError::MissingRequiredField));
+    }
+
+    #[test]
+    fn test_convenience_functions() {
+        let env = Env::default();
+
+        let json_cid = Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
+        let model_hash = Bytes::from_slice(&env, b"a1b2c3d4e5f6789012345678901234567890abcdef");
+        let name = Bytes::from_slice(&env, b"TestAgent");
+        let description = Bytes::from_slice(&env, b"A test agent");
+        let version = Bytes::from_slice(&env, b"1.0.0");
+
+        let result = convenience::validate_metadata_quick(
+            &env,
+            json_cid,
+            model_hash,
+            name,
+            description,
+            version
+        );
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_custom_validator_configurations() {
+        let env = Env::default();
+
+        // Test with custom CID configuration
+        let cid_config = ValidatorConfig::new()
+            .with_length_bounds(5, 150) // More lenient bounds
+            .strict(false);
+
+        let hash_config = ValidatorConfig::new()
+            .with_length_bounds(20, 200) // More lenient bounds
+            .strict(false);
+
+        let json_cid = Bytes::from_slice(&env, b"short"); // Would normally fail
+        let model_hash = Bytes::from_slice(&env, b"short_hash"); // Would normally fail
+        let name = Bytes::from_slice(&env, b"TestAgent");
+        let description = Bytes::from_slice(&env, b"A test agent");
+        let version = Bytes::from_slice(&env, b"1.0.0");
+        let extra_fields = Vec::new(&env);
+
+        let result = convenience::validate_with_custom_config(
+            &env,
+            json_cid,
+            model_hash,
+            name,
+            description,
+            version,
+            extra_fields,
+            cid_config,
+            hash_config
+        );
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_validator_accessors() {
+        let validator = MetadataValidator::new();
+
+        // Test that we can access individual validators
+        let cid_validator = validator.cid_validator();
+        let hash_validator = validator.hash_validator();
+        let name_validator = validator.name_validator();
+
+        assert_eq!(cid_validator.name(), "CIDValidator");
+        assert_eq!(hash_validator.name(), "HashValidator");
+        assert_eq!(name_validator.name(), "BytesValidator");
+    }
+
+    #[test]
+    fn test_json_blob_cid_rejected() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().reject_json_blob_cid(true)
+        );
+
+        let blob = Bytes::from_slice(&env, b"{\"name\":\"TestAgent\",\"version\":\"1.0.0\"}");
+        let array_blob = Bytes::from_slice(&env, b"[\"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG\"]");
+        let real_cid = Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
+
+        assert_eq!(validator.validate_cid(&env, &blob), Err(MetadataError::InvalidCidFormat));
+        assert_eq!(
+            validator.validate_cid(&env, &array_blob),
+            Err(MetadataError::InvalidCidFormat)
+        );
+        assert!(validator.validate_cid(&env, &real_cid).is_ok());
+
+        // Off by default: only the plain length checks apply
+        assert!(MetadataValidator::new().validate_cid(&env, &blob).is_ok());
+        let strict = MetadataValidator::new().with_parser_config(ParserConfig::strict_production());
+        assert_eq!(strict.validate_cid(&env, &blob), Err(MetadataError::InvalidCidFormat));
+    }
+
+    #[test]
+    fn test_validation_stats_match_field_sizes() {
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+
+        let mut extra_fields = Vec::new(&env);
+        extra_fields.push_back((
+            Bytes::from_slice(&env, b"license"),
+            Bytes::from_slice(&env, b"MIT"),
+        ));
+        extra_fields.push_back((
+            Bytes::from_slice(&env, b"homepage"),
+            Bytes::from_slice(&env, b"https://example.com"),
+        ));
+
+        let (_, stats) = validator
+            .validate_and_parse_with_stats(
+                &env,
+                Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+                Bytes::from_slice(&env, b"a1b2c3d4e5f6789012345678901234567890abcdef"),
+                Bytes::from_slice(&env, b"TestAgent"),
+                Bytes::from_slice(&env, b"A test agent"),
+                Bytes::from_slice(&env, b"1.0.0"),
+                extra_fields
+            )
+            .unwrap();
+
+        assert_eq!(stats.cid_bytes, 46);
+        assert_eq!(stats.hash_bytes, 42);
+        assert_eq!(stats.name_bytes, 9);
+        assert_eq!(stats.description_bytes, 12);
+        assert_eq!(stats.version_bytes, 5);
+        assert_eq!(stats.extra_bytes, 7 + 3 + 8 + 19);
+        assert_eq!(stats.total_bytes(), 46 + 42 + 9 + 12 + 5 + 37);
+
+        // Sizes describe the input, not the normalized output
+        let raw = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().store_raw_hash(true)
+        );
+        let (metadata, stats) = raw
+            .validate_and_parse_with_stats(
+                &env,
+                Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+                Bytes::from_slice(
+                    &env,
+                    b"d4f0bc5a29de06b510f9aa428f1eedba926012b591fef7a518e776a7c9bd1824"
+                ),
+                Bytes::from_slice(&env, b"TestAgent"),
+                Bytes::from_slice(&env, b"A test agent"),
+                Bytes::from_slice(&env, b"1.0.0"),
+                Vec::new(&env)
+            )
+            .unwrap();
+
+        assert_eq!(metadata.model_hash.len(), 32);
+        assert_eq!(stats.hash_bytes, 64);
+    }
+
+    #[test]
+    fn test_blank_text_rejected() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().reject_blank_text(true)
+        );
+
+        let parse = |name: &[u8], description: &[u8]| {
+            validator.validate_and_parse(
+                &env,
+                Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+                Bytes::from_slice(&env, b"a1b2c3d4e5f6789012345678901234567890abcdef"),
+                Bytes::from_slice(&env, name),
+                Bytes::from_slice(&env, description),
+                Bytes::from_slice(&env, b"1.0.0"),
+                Vec::new(&env)
+            )
+        };
+
+        assert_eq!(parse(b"   ", b"A test agent"), Err(MetadataError::MissingRequiredField));
+        assert_eq!(parse(b"TestAgent", b" \t\r\n"), Err(MetadataError::MissingRequiredField));
+        assert!(parse(b"  A ", b"A test agent").is_ok());
+
+        // Without the flag, whitespace-only names only need to be non-empty
+        let default_validator = MetadataValidator::new();
+        let result = default_validator.validate_and_parse(
+            &env,
+            Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+            Bytes::from_slice(&env, b"a1b2c3d4e5f6789012345678901234567890abcdef"),
+            Bytes::from_slice(&env, b"   "),
+            Bytes::from_slice(&env, b"A test agent"),
+            Bytes::from_slice(&env, b"1.0.0"),
+            Vec::new(&env)
+        );
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_created_at_validation() {
+        let env = Env::default();
+        env.ledger().set_timestamp(1_700_000_000);
+
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().validate_created_at(true, 60)
+        );
+
+        let parse = |created_at: &[u8]| {
+            let mut extra_fields = Vec::new(&env);
+            extra_fields.push_back((
+                Bytes::from_slice(&env, b"created_at"),
+                Bytes::from_slice(&env, created_at),
+            ));
+            validator.validate_and_parse(
+                &env,
+                Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+                Bytes::from_slice(&env, b"a1b2c3d4e5f6789012345678901234567890abcdef"),
+                Bytes::from_slice(&env, b"TestAgent"),
+                Bytes::from_slice(&env, b"A test agent"),
+                Bytes::from_slice(&env, b"1.0.0"),
+                extra_fields
+            )
+        };
+
+        assert!(parse(b"1600000000").is_ok());
+        assert!(parse(b"1700000030").is_ok()); // within skew
+        assert_eq!(parse(b"4102444800"), Err(MetadataError::InvalidStructure));
+        assert_eq!(parse(b"yesterday"), Err(MetadataError::InvalidStructure));
+    }
+
+    #[test]
+    fn test_extra_field_diff() {
+        let env = Env::default();
+        let field = |k: &[u8], v: &[u8]| (Bytes::from_slice(&env, k), Bytes::from_slice(&env, v));
+
+        let mut old_fields = Vec::new(&env);
+        old_fields.push_back(field(b"license", b"MIT"));
+        old_fields.push_back(field(b"homepage", b"https://old.example.com"));
+        old_fields.push_back(field(b"kind", b"trading"));
+
+        let mut new_fields = Vec::new(&env);
+        new_fields.push_back(field(b"kind", b"trading"));
+        new_fields.push_back(field(b"homepage", b"https://new.example.com"));
+        new_fields.push_back(field(b"content_type", b"onnx"));
+
+        let old = AgentMetadata {
+            json_cid: Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+            model_hash: Bytes::from_slice(&env, b"a1b2c3d4e5f6789012345678901234567890abcdef"),
+            name: Bytes::from_slice(&env, b"TestAgent"),
+            description: Bytes::from_slice(&env, b"A test agent"),
+            version: Bytes::from_slice(&env, b"1.0.0"),
+            extra_fields: old_fields,
+        };
+        let mut new = old.clone();
+        new.extra_fields = new_fields;
+
+        let diff = old.extra_field_diff(&env, &new);
+
+        assert_eq!(diff.len(), 3);
+        let (key, value) = field(b"license", b"MIT");
+        assert_eq!(diff.get(0), Some(ExtraChange::Removed(key, value)));
+        let (key, old_value) = field(b"homepage", b"https://old.example.com");
+        let new_value = Bytes::from_slice(&env, b"https://new.example.com");
+        assert_eq!(diff.get(1), Some(ExtraChange::Changed(key, old_value, new_value)));
+        let (key, value) = field(b"content_type", b"onnx");
+        assert_eq!(diff.get(2), Some(ExtraChange::Added(key, value)));
+
+        assert_eq!(old.extra_field_diff(&env, &old).len(), 0);
+    }
+
+    #[test]
+    fn test_strict_production_preset() {
+        let env = Env::default();
+        let default_validator = MetadataValidator::new();
+        let strict_validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::strict_production()
+        );
+
+        let parse = |validator: &MetadataValidator, json_cid: &[u8], key: &[u8]| {
+            let mut extra_fields = Vec::new(&env);
+            extra_fields.push_back((Bytes::from_slice(&env, key), Bytes::from_slice(&env, b"x")));
+            validator.validate_and_parse(
+                &env,
+                Bytes::from_slice(&env, json_cid),
+                Bytes::from_slice(&env, b"a1b2c3d4e5f6789012345678901234567890abcdef"),
+                Bytes::from_slice(&env, b"TestAgent"),
+                Bytes::from_slice(&env, b"A test agent"),
+                Bytes::from_slice(&env, b"1.0.0"),
+                extra_fields
+            )
+        };
+
+        let cid_v0: &[u8] = b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+        let cid_v1: &[u8] = b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+
+        assert!(parse(&default_validator, cid_v0, b"license").is_ok());
+        assert_eq!(
+            parse(&strict_validator, cid_v0, b"license"),
+            Err(MetadataError::InvalidCidFormat)
+        );
+        assert!(parse(&strict_validator, cid_v1, b"license").is_ok());
+
+        assert!(parse(&default_validator, cid_v1, b"_internal").is_ok());
+        assert_eq!(
+            parse(&strict_validator, cid_v1, b"_internal"),
+            Err(MetadataError::InvalidStructure)
+        );
+    }
+
+    #[test]
+    fn test_allowed_content_types() {
+        let env = Env::default();
+        let mut allowed = Vec::new(&env);
+        allowed.push_back(Bytes::from_slice(&env, b"onnx"));
+        allowed.push_back(Bytes::from_slice(&env, b"gguf"));
+        allowed.push_back(Bytes::from_slice(&env, b"safetensors"));
+
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().with_allowed_content_types(allowed)
+        );
+
+        assert!(parse_with_extras(&env, &validator, &[(b"content_type", b"gguf")]).is_ok());
+        assert_eq!(
+            parse_with_extras(&env, &validator, &[(b"content_type", b"pickle")]),
+            Err(MetadataError::InvalidStructure)
+        );
+        assert_eq!(
+            parse_with_extras(&env, &validator, &[(b"license", b"MIT")]),
+            Err(MetadataError::InvalidStructure)
+        );
+
+        // Without an allowlist the field is optional and unchecked
+        let default_validator = MetadataValidator::new();
+        assert!(parse_with_extras(&env, &default_validator, &[(b"license", b"MIT")]).is_ok());
+        assert!(
+            parse_with_extras(&env, &default_validator, &[(b"content_type", b"pickle")]).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_max_extra_key_length() {
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+
+        let key_at_limit = [b'k'; 64];
+        let key_over_limit = [b'k'; 65];
+
+        assert!(parse_with_extras(&env, &validator, &[(&key_at_limit, b"v")]).is_ok());
+        assert_eq!(
+            parse_with_extras(&env, &validator, &[(&key_over_limit, b"v")]),
+            Err(MetadataError::InvalidLength)
+        );
+
+        // Values are not subject to the key limit
+        assert!(parse_with_extras(&env, &validator, &[(b"k", &[b'v'; 128])]).is_ok());
+    }
+
+    #[test]
+    fn test_accept_base58_hash() {
+        let env = Env::default();
+        let strict_hash = ValidatorConfig::new().with_length_bounds(32, 128).strict(true);
+        let base58_hash = Bytes::from_slice(&env, b"FLELiEbZiFHjRSx9uZssckQd8RtPu3nLCKFB1gTuXnNX");
+        let hex_hash = Bytes::from_slice(
+            &env,
+            b"d4f0bc5a29de06b510f9aa428f1eedba926012b591fef7a518e776a7c9bd1824"
+        );
+
+        let build = |config: ParserConfig| {
+            MetadataValidator::with_config(
+                ValidatorConfig::new().with_length_bounds(10, 100),
+                strict_hash.clone(),
+                ValidatorConfig::new().with_length_bounds(1, 100),
+                ValidatorConfig::new().with_length_bounds(1, 1000),
+                ValidatorConfig::new().with_length_bounds(1, 50)
+            ).with_parser_config(config)
+        };
+
+        let hex_only = build(ParserConfig::new());
+        assert!(hex_only.validate_model_hash(&env, &hex_hash).is_ok());
+        assert_eq!(
+            hex_only.validate_model_hash(&env, &base58_hash),
+            Err(MetadataError::HashVerificationFailed)
+        );
+
+        let with_base58 = build(ParserConfig::new().accept_base58_hash(true));
+        assert!(with_base58.validate_model_hash(&env, &hex_hash).is_ok());
+        assert!(with_base58.validate_model_hash(&env, &base58_hash).is_ok());
+        assert_eq!(
+            hash::decode_any(&env, &hex_hash),
+            hash::decode_any(&env, &base58_hash)
+        );
+    }
+
+    #[test]
+    fn test_accept_base64_hash() {
+        let env = Env::default();
+        let padded = Bytes::from_slice(&env, b"1PC8WineBrUQ+apCjx7tupJgErWR/velGOd2p8m9GCQ=");
+        let unpadded = Bytes::from_slice(&env, b"1PC8WineBrUQ+apCjx7tupJgErWR/velGOd2p8m9GCQ");
+        let build = |config: ParserConfig| {
+            MetadataValidator::with_config(
+                ValidatorConfig::new().with_length_bounds(10, 100),
+                ValidatorConfig::new().with_length_bounds(32, 128).strict(true),
+                ValidatorConfig::new().with_length_bounds(1, 100),
+                ValidatorConfig::new().with_length_bounds(1, 1000),
+                ValidatorConfig::new().with_length_bounds(1, 50)
+            ).with_parser_config(config)
+        };
+
+        assert_eq!(
+            build(ParserConfig::new()).validate_model_hash(&env, &padded),
+            Err(MetadataError::HashVerificationFailed)
+        );
+
+        let with_base64 = build(ParserConfig::new().accept_base64_hash(true));
+        let digest = with_base64.parse_model_hash(&env, &padded).unwrap();
+        assert_eq!(digest.len(), hash::RAW_DIGEST_LENGTH);
+        assert_eq!(with_base64.parse_model_hash(&env, &unpadded), Ok(digest));
+    }
+
+    #[test]
+    fn test_merge_extra_policies() {
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+
+        let ours = parse_with_extras(
+            &env,
+            &validator,
+            &[(b"license", b"MIT"), (b"kind", b"trading")]
+        ).unwrap();
+        let theirs = parse_with_extras(
+            &env,
+            &validator,
+            &[(b"kind", b"analysis"), (b"homepage", b"https://example.com")]
+        ).unwrap();
+
+        let key = |k: &[u8]| Bytes::from_slice(&env, k);
+
+        let merged = ours.merge_extra(&env, &theirs, MergePolicy::PreferSelf).unwrap();
+        assert_eq!(merged.extra_fields.len(), 3);
+        assert_eq!(merged.get_extra(&key(b"kind")), Some(key(b"trading")));
+        assert_eq!(merged.get_extra(&key(b"homepage")), Some(key(b"https://example.com")));
+
+        let merged = ours.merge_extra(&env, &theirs, MergePolicy::PreferOther).unwrap();
+        assert_eq!(merged.extra_fields.len(), 3);
+        assert_eq!(merged.get_extra(&key(b"kind")), Some(key(b"analysis")));
+        assert_eq!(merged.get_extra(&key(b"license")), Some(key(b"MIT")));
+
+        assert_eq!(
+            ours.merge_extra(&env, &theirs, MergePolicy::FailOnConflict),
+            Err(MetadataError::DuplicateExtraField)
+        );
+        // Identical values on an overlapping key are not a conflict
+        assert!(ours.merge_extra(&env, &ours, MergePolicy::FailOnConflict).is_ok());
+    }
+
+    #[test]
+    fn test_env_free_validation_matches_validator() {
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+
+        let inputs: [&[u8]; 7] = [
+            b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG",
+            b"a1b2c3d4e5f6789012345678901234567890abcdef",
+            b"short",
+            b"{\"name\":\"TestAgent\",\"version\":\"1.0.0\"}",
+            b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+            b"BAFYBEIGDYRZT5SFP7UDM7HU76UH7Y26NF3EFUYLQABF3OCLGTQY55FBZDI",
+            b"bafyBEIGDYRZT5SFP7UDM7HU76UH7Y26NF3EFUYLQABF3OCLGTQY55FBZDI",
+        ];
+
+        for input in inputs {
+            let bytes = Bytes::from_slice(&env, input);
+            assert_eq!(cid::validate(input), validator.validate_cid(&env, &bytes));
+            assert_eq!(hash::validate(input), validator.validate_model_hash(&env, &bytes));
+        }
+    }
+
+    #[test]
+    fn test_validate_extra_page() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().forbid_reserved_keys(true)
+        );
+
+        let mut fields = Vec::new(&env);
+        for key in [b"license", b"kind_01", b"website", b"_hidden"] {
+            fields.push_back((Bytes::from_slice(&env, key), Bytes::from_slice(&env, b"x")));
+        }
+
+        // First page is clean, second page carries the reserved key
+        assert!(validator.validate_extra_page(&env, fields.clone(), 0, 2).is_ok());
+        assert_eq!(
+            validator.validate_extra_page(&env, fields.clone(), 2, 2),
+            Err(MetadataError::InvalidStructure)
+        );
+
+        // Pages may not run past the end
+        assert_eq!(
+            validator.validate_extra_page(&env, fields.clone(), 3, 2),
+            Err(MetadataError::InvalidLength)
+        );
+        assert!(validator.validate_extra_page(&env, fields, 4, 0).is_ok());
+    }
+
+    #[test]
+    fn test_canonical_json_ignores_extra_order() {
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+
+        let first = parse_with_extras(
+            &env,
+            &validator,
+            &[(b"license", b"MIT"), (b"kind", b"trading")]
+        ).unwrap();
+        let second = parse_with_extras(
+            &env,
+            &validator,
+            &[(b"kind", b"trading"), (b"license", b"MIT")]
+        ).unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(first.to_canonical_json(&env), second.to_canonical_json(&env));
+        assert_eq!(
+            first.to_canonical_json(&env),
+            Bytes::from_slice(
+                &env,
+                b"{\"description\":\"A test agent\",\"extra\":{\"kind\":\"trading\",\"license\":\"MIT\"},\"json_cid\":\"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG\",\"model_hash\":\"a1b2c3d4e5f6789012345678901234567890abcdef\",\"name\":\"TestAgent\",\"version\":\"1.0.0\"}"
+            )
+        );
+    }
+
+    #[test]
+    fn test_allowed_kinds() {
+        let env = Env::default();
+        let mut kinds = Vec::new(&env);
+        kinds.push_back(Bytes::from_slice(&env, b"trading"));
+        kinds.push_back(Bytes::from_slice(&env, b"analysis"));
+        kinds.push_back(Bytes::from_slice(&env, b"execution"));
+
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().with_allowed_kinds(kinds)
+        );
+
+        assert!(parse_with_extras(&env, &validator, &[(b"kind", b"analysis")]).is_ok());
+        assert_eq!(
+            parse_with_extras(&env, &validator, &[(b"kind", b"gaming")]),
+            Err(MetadataError::InvalidStructure)
+        );
+        assert_eq!(
+            parse_with_extras(&env, &validator, &[(b"content_type", b"onnx")]),
+            Err(MetadataError::InvalidStructure)
+        );
+    }
+
+    #[test]
+    fn test_extra_fingerprint() {
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+
+        let base = parse_with_extras(
+            &env,
+            &validator,
+            &[(b"license", b"MIT"), (b"kind", b"trading")]
+        ).unwrap();
+        let fingerprint = base.extra_fingerprint(&env);
+        assert_eq!(fingerprint.len(), 32);
+
+        let mut renamed = base.clone();
+        renamed.name = Bytes::from_slice(&env, b"RenamedAgent");
+        assert_eq!(renamed.extra_fingerprint(&env), fingerprint);
+
+        let reordered = parse_with_extras(
+            &env,
+            &validator,
+            &[(b"kind", b"trading"), (b"license", b"MIT")]
+        ).unwrap();
+        assert_eq!(reordered.extra_fingerprint(&env), fingerprint);
+
+        let changed = parse_with_extras(
+            &env,
+            &validator,
+            &[(b"license", b"Apache-2.0"), (b"kind", b"trading")]
+        ).unwrap();
+        assert_ne!(changed.extra_fingerprint(&env), fingerprint);
+    }
+
+    #[test]
+    fn test_parse_model_hash_returns_digest() {
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+
+        let hex_hash = Bytes::from_slice(
+            &env,
+            b"00112233445566778899AABBCCDDEEFF00112233445566778899aabbccddeeff"
+        );
+        let expected = Bytes::from_array(
+            &env,
+            &[
+                0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb, 0xcc, 0xdd,
+                0xee, 0xff, 0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88, 0x99, 0xaa, 0xbb,
+                0xcc, 0xdd, 0xee, 0xff,
+            ]
+        );
+
+        assert_eq!(validator.parse_model_hash(&env, &hex_hash), Ok(expected));
+        assert_eq!(
+            validator.parse_model_hash(&env, &Bytes::from_slice(&env, b"abc")),
+            Err(MetadataError::HashVerificationFailed)
+        );
+
+        // Long enough for the lenient validator, but not a digest in any encoding
+        let text = Bytes::from_slice(&env, b"this-is-a-model-description-not-a-hash");
+        assert!(validator.validate_model_hash(&env, &text).is_ok());
+        assert_eq!(
+            validator.parse_model_hash(&env, &text),
+            Err(MetadataError::HashVerificationFailed)
+        );
+    }
+
+    #[test]
+    fn test_name_matches_loosely() {
+        let env = Env::default();
+        let mut metadata = parse_with_extras(&env, &MetadataValidator::new(), &[]).unwrap();
+        metadata.name = Bytes::from_slice(&env, b"  MyAgent ");
+
+        assert!(metadata.name_matches_loosely(&env, &Bytes::from_slice(&env, b"myagent")));
+        assert!(metadata.name_matches_loosely(&env, &Bytes::from_slice(&env, b"MYAGENT\t")));
+        assert!(!metadata.name_matches_loosely(&env, &Bytes::from_slice(&env, b"my agent")));
+        assert!(!metadata.name_matches_loosely(&env, &Bytes::from_slice(&env, b"OtherAgent")));
+    }
+
+    #[test]
+    fn test_printable_extra_fields() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().require_printable_text(true)
+        );
+
+        assert!(parse_with_extras(&env, &validator, &[(b"license", b"MIT")]).is_ok());
+        assert_eq!(
+            parse_with_extras(&env, &validator, &[(b"license", b"M\x01IT")]),
+            Err(MetadataError::InvalidStructure)
+        );
+        assert_eq!(
+            parse_with_extras(&env, &validator, &[(b"my license", b"MIT")]),
+            Err(MetadataError::InvalidStructure)
+        );
+
+        // Without the flag control bytes pass through
+        assert!(
+            parse_with_extras(&env, &MetadataValidator::new(), &[(b"license", b"M\x01IT")]).is_ok()
+        );
+    }
+
+    #[test]
+    fn test_stable_id() {
+        let env = Env::default();
+        let first = parse_with_extras(&env, &MetadataValidator::new(), &[]).unwrap();
+
+        let mut second = first.clone();
+        second.json_cid = Bytes::from_slice(
+            &env,
+            b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
+        );
+        second.description = Bytes::from_slice(&env, b"Another description");
+        assert_eq!(first.stable_id(&env), second.stable_id(&env));
+        assert_eq!(first.stable_id(&env).len(), 32);
+
+        let mut bumped = first.clone();
+        bumped.version = Bytes::from_slice(&env, b"1.0.1");
+        assert_ne!(first.stable_id(&env), bumped.stable_id(&env));
+
+        // Moving bytes across the separator changes the id
+        let mut shifted = first.clone();
+        shifted.name = Bytes::from_slice(&env, b"TestAgent1");
+        shifted.version = Bytes::from_slice(&env, b".0.0");
+        assert_ne!(first.stable_id(&env), shifted.stable_id(&env));
+    }
+
+    #[test]
+    fn test_allow_empty_description() {
+        let env = Env::default();
+
+        let parse = |validator: &MetadataValidator, name: &[u8], description: &[u8]| {
+            validator.validate_and_parse(
+                &env,
+                Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+                Bytes::from_slice(&env, b"a1b2c3d4e5f6789012345678901234567890abcdef"),
+                Bytes::from_slice(&env, name),
+                Bytes::from_slice(&env, description),
+                Bytes::from_slice(&env, b"1.0.0"),
+                Vec::new(&env)
+            )
+        };
+
+        let default_validator = MetadataValidator::new();
+        assert_eq!(
+            parse(&default_validator, b"TestAgent", b""),
+            Err(MetadataError::MissingRequiredField)
+        );
+
+        let permissive = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().allow_empty_description(true).reject_blank_text(true)
+        );
+        assert!(parse(&permissive, b"TestAgent", b"").is_ok());
+        assert_eq!(parse(&permissive, b"", b""), Err(MetadataError::MissingRequiredField));
+        // A non-empty but blank description is still blank text
+        assert_eq!(
+            parse(&permissive, b"TestAgent", b"   "),
+            Err(MetadataError::MissingRequiredField)
+        );
+    }
+
+    #[test]
+    fn test_asset_cids() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().validate_asset_cids(true)
+        );
+
+        let two_assets: &[u8] =
+            b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG,bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+        let metadata = parse_with_extras(&env, &validator, &[(b"assets", two_assets)]).unwrap();
+
+        let assets = metadata.asset_cids(&env);
+        assert_eq!(assets.len(), 2);
+        assert_eq!(
+            assets.get(0),
+            Some(Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"))
+        );
+
+        let malformed: &[u8] = b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG,Qm";
+        assert_eq!(
+            parse_with_extras(&env, &validator, &[(b"assets", malformed)]),
+            Err(MetadataError::InvalidCidFormat)
+        );
+        assert!(parse_with_extras(&env, &MetadataValidator::new(), &[(b"assets", malformed)]).is_ok());
+
+        let no_assets = parse_with_extras(&env, &validator, &[]).unwrap();
+        assert_eq!(no_assets.asset_cids(&env).len(), 0);
+    }
+
+    #[test]
+    fn test_malformed_extra_fields_rejected() {
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+
+        let mut raw: Vec<Val> = Vec::new(&env);
+        raw.push_back(
+            (Bytes::from_slice(&env, b"license"), Bytes::from_slice(&env, b"MIT")).into_val(&env)
+        );
+        raw.push_back((Bytes::from_slice(&env, b"rank"), 7u32).into_val(&env));
+        let malformed = Vec::<(Bytes, Bytes)>::try_from_val(&env, raw.as_val()).unwrap();
+
+        let result = validator.validate_and_parse(
+            &env,
+            Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+            Bytes::from_slice(&env, b"a1b2c3d4e5f6789012345678901234567890abcdef"),
+            Bytes::from_slice(&env, b"TestAgent"),
+            Bytes::from_slice(&env, b"A test agent"),
+            Bytes::from_slice(&env, b"1.0.0"),
+            malformed
+        );
+
+        assert_eq!(result, Err(MetadataError::InvalidStructure));
+    }
+
+    #[test]
+    fn test_normalize_cid() {
+        let env = Env::default();
+
+        let normalized = convenience::normalize_cid(
+            &env,
+            Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG")
+        );
+        assert_eq!(
+            normalized,
+            Ok(
+                Bytes::from_slice(
+                    &env,
+                    b"bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho34"
+                )
+            )
+        );
+
+        assert_eq!(
+            convenience::normalize_cid(&env, Bytes::from_slice(&env, b"Qm")),
+            Err(MetadataError::InvalidCidFormat)
+        );
+        assert_eq!(
+            convenience::normalize_cid(&env, Bytes::from_slice(&env, b"definitely-not-a-cid")),
+            Err(MetadataError::InvalidCidFormat)
+        );
+    }
+
+    #[test]
+    fn test_cidv1_case_policy() {
+        let env = Env::default();
+        let upper = Bytes::from_slice(
+            &env,
+            b"BAFYBEIGDYRZT5SFP7UDM7HU76UH7Y26NF3EFUYLQABF3OCLGTQY55FBZDI"
+        );
+
+        let with_case = |policy: CasePolicy| {
+            MetadataValidator::new().with_parser_config(ParserConfig::new().cidv1_case(policy))
+        };
+
+        assert_eq!(
+            with_case(CasePolicy::LowerOnly).validate_cid(&env, &upper),
+            Err(MetadataError::InvalidCidFormat)
+        );
+        assert!(with_case(CasePolicy::UpperOnly).validate_cid(&env, &upper).is_ok());
+        assert!(with_case(CasePolicy::Either).validate_cid(&env, &upper).is_ok());
+    }
+
+    #[test]
+    fn test_canonical_bytes_and_footprint() {
+        let env = Env::default();
+        let metadata = parse_with_extras(
+            &env,
+            &MetadataValidator::new(),
+            &[(b"license", b"MIT"), (b"kind", b"trading")]
+        ).unwrap();
+
+        let encoded = metadata.to_bytes(&env);
+        assert_eq!(metadata.storage_footprint(), encoded.len());
+
+        // Decoding yields the canonical (sorted) extra field order
+        let decoded = AgentMetadata::from_bytes(&env, &encoded).unwrap();
+        assert_eq!(decoded.extra_fields, metadata.sorted_extra_fields(&env));
+        assert_eq!(decoded.name, metadata.name);
+
+        assert_eq!(
+            AgentMetadata::from_bytes(&env, &encoded.slice(0..encoded.len() - 1)),
+            Err(MetadataError::InvalidStructure)
+        );
+    }
+
+    #[test]
+    fn test_encoding_version_byte() {
+        let env = Env::default();
+        let metadata = MetadataValidator::new().validate_input(&env, sample_input(&env)).unwrap();
+
+        let mut encoded = metadata.to_bytes(&env);
+        assert_eq!(encoded.first(), Some(AgentMetadata::ENCODING_VERSION));
+        assert_eq!(AgentMetadata::from_bytes(&env, &encoded), Ok(metadata));
+
+        encoded.set(0, AgentMetadata::ENCODING_VERSION + 1);
+        assert_eq!(AgentMetadata::from_bytes(&env, &encoded), Err(MetadataError::InvalidStructure));
+    }
+
+    #[test]
+    fn test_post_check() {
+        fn require_org_prefix(_env: &Env, metadata: &AgentMetadata) -> Result<(), MetadataError> {
+            let prefix = b"org:";
+            let matches = metadata.name.len() >= 4 &&
+                (0..4).all(|i| metadata.name.get_unchecked(i) == prefix[i as usize]);
+            if matches {
+                Ok(())
+            } else {
+                Err(MetadataError::InvalidStructure)
+            }
+        }
+
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_post_check(require_org_prefix);
+
+        let parse = |name: &[u8]| {
+            validator.validate_and_parse(
+                &env,
+                Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+                Bytes::from_slice(&env, b"a1b2c3d4e5f6789012345678901234567890abcdef"),
+                Bytes::from_slice(&env, name),
+                Bytes::from_slice(&env, b"A test agent"),
+                Bytes::from_slice(&env, b"1.0.0"),
+                Vec::new(&env)
+            )
+        };
+
+        assert!(parse(b"org:TestAgent").is_ok());
+        assert_eq!(parse(b"TestAgent"), Err(MetadataError::InvalidStructure));
+    }
+
+    #[test]
+    fn test_deep_scan() {
+        let env = Env::default();
+        let shallow = MetadataValidator::new();
+        let deep = MetadataValidator::new().with_parser_config(ParserConfig::new().deep_scan(true));
+
+        let parse = |validator: &MetadataValidator, model_hash: &[u8]| {
+            validator.validate_and_parse(
+                &env,
+                Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"),
+                Bytes::from_slice(&env, model_hash),
+                Bytes::from_slice(&env, b"TestAgent"),
+                Bytes::from_slice(&env, b"A test agent"),
+                Bytes::from_slice(&env, b"1.0.0"),
+                Vec::new(&env)
+            )
+        };
+
+        // Right length, but not a hex digest
+        let not_hex: &[u8] = b"zzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzz";
+        assert!(parse(&shallow, not_hex).is_ok());
+        assert_eq!(parse(&deep, not_hex), Err(MetadataError::HashVerificationFailed));
+
+        let hex: &[u8] = b"a1b2c3d4e5f6789012345678901234567890abcdef";
+        assert!(parse(&deep, hex).is_ok());
+    }
+
+    #[test]
+    fn test_sig_cid_validation() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().validate_sig_cid(true).reject_json_blob_cid(true)
+        );
+
+        let valid_sig: &[u8] = b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+        let metadata = parse_with_extras(&env, &validator, &[(b"sig_cid", valid_sig)]).unwrap();
+        assert_eq!(metadata.sig_cid(&env), Some(Bytes::from_slice(&env, valid_sig)));
+
+        assert_eq!(
+            parse_with_extras(&env, &validator, &[(b"sig_cid", b"{\"sig\":\"abc\"}")]),
+            Err(MetadataError::InvalidCidFormat)
+        );
+        assert_eq!(
+            parse_with_extras(&env, &validator, &[(b"sig_cid", b"Qm")]),
+            Err(MetadataError::InvalidCidFormat)
+        );
+
+        let unsigned = parse_with_extras(&env, &validator, &[]).unwrap();
+        assert_eq!(unsigned.sig_cid(&env), None);
+    }
+
+    #[test]
+    fn test_inspect_field_encodings() {
+        use inspect::{ CidEncoding, HashEncoding, TextEncoding };
+
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+
+        let mut input = sample_input(&env);
+        input.description = Bytes::from_slice(&env, "caf\u{e9} agent".as_bytes());
+        let encodings = validator.inspect(&env, &input);
+
+        assert_eq!(encodings.json_cid, CidEncoding::V0);
+        assert_eq!(encodings.model_hash, HashEncoding::Hex);
+        assert_eq!(encodings.name, TextEncoding::Ascii);
+        assert_eq!(encodings.description, TextEncoding::Utf8);
+
+        // Invalid input is reported, not rejected
+        input.json_cid = Bytes::from_slice(&env, b"{\"not\":\"a cid\"}");
+        input.model_hash = Bytes::from_slice(&env, b"FLELiEbZiFHjRSx9uZssckQd8RtPu3nLCKFB1gTuXnNX");
+        input.name = Bytes::from_slice(&env, b"Bad\x01Name");
+        let encodings = validator.inspect(&env, &input);
+
+        assert_eq!(encodings.json_cid, CidEncoding::Unknown);
+        assert_eq!(encodings.model_hash, HashEncoding::Base58);
+        assert_eq!(encodings.name, TextEncoding::Unknown);
+    }
+
+    #[test]
+    fn test_name_must_be_dns_label() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().name_must_be_dns_label(true)
+        );
+
+        let parse = |name: &[u8]| {
+            let mut input = sample_input(&env);
+            input.name = Bytes::from_slice(&env, name);
+            validator.validate_input(&env, input)
+        };
+
+        assert!(parse(b"trading-agent").is_ok());
+        assert_eq!(parse(b"trading_agent"), Err(MetadataError::InvalidStructure));
+        assert_eq!(parse(b"trading-agent-"), Err(MetadataError::InvalidStructure));
+    }
+
+    #[test]
+    fn test_binary_diff_round_trip() {
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+        let base = parse_with_extras(&env, &validator, &[(b"kind", b"trader")]).unwrap();
+
+        let mut target = base.clone();
+        target.version = Bytes::from_slice(&env, b"1.1.0");
+        target.extra_fields.push_back((
+            Bytes::from_slice(&env, b"content_type"),
+            Bytes::from_slice(&env, b"application/json"),
+        ));
+
+        let diff = base.binary_diff(&env, &target);
+        assert_eq!(base.apply_diff(&env, &diff), Ok(target.clone()));
+        assert!(diff.len() < target.to_bytes(&env).len());
+
+        let unchanged = base.binary_diff(&env, &base);
+        assert_eq!(unchanged, Bytes::from_array(&env, &[0, 0, 0, 0]));
+        assert_eq!(base.apply_diff(&env, &unchanged), Ok(base.clone()));
+
+        let unknown = Bytes::from_array(&env, &[0, 0, 0, 0x40]);
+        assert_eq!(base.apply_diff(&env, &unknown), Err(MetadataError::InvalidStructure));
+    }
+
+    #[test]
+    fn test_cid_cache_skips_repeat_validation() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_cid_cache();
+        let cid = Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
+
+        for _ in 0..5 {
+            assert_eq!(validator.validate_cid(&env, &cid), Ok(()));
+        }
+        // Four of five calls were answered from the cache, so the check ran once
+        assert_eq!(validator.cid_cache_hits(), 4);
+
+        let invalid = Bytes::from_slice(&env, b"not-a-cid");
+        assert_eq!(validator.validate_cid(&env, &invalid), Err(MetadataError::InvalidCidFormat));
+        assert_eq!(validator.validate_cid(&env, &invalid), Err(MetadataError::InvalidCidFormat));
+        assert_eq!(validator.cid_cache_hits(), 5);
+
+        assert_eq!(MetadataValidator::new().cid_cache_hits(), 0);
+    }
+
+    #[test]
+    fn test_register_unique_name() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        let validator = MetadataValidator::new();
+
+        env.as_contract(&contract_id, || {
+            let first = validator.validate_input(&env, sample_input(&env)).unwrap();
+            let mut second = first.clone();
+            second.name = Bytes::from_slice(&env, b"OtherAgent");
+
+            let first_id = Bytes::from_slice(&env, b"agent-1");
+            let second_id = Bytes::from_slice(&env, b"agent-2");
+            assert_eq!(validator.register_unique_name(&env, &first_id, &first), Ok(()));
+            assert_eq!(validator.register_unique_name(&env, &second_id, &second), Ok(()));
+            assert_eq!(validator.register_unique_name(&env, &first_id, &first), Ok(()));
+
+            assert_eq!(
+                validator.register_unique_name(&env, &second_id, &first),
+                Err(MetadataError::NameTaken)
+            );
+            assert_eq!(storage::name_owner(&env, &first.name), Some(first_id));
+        });
+    }
+
+    #[test]
+    fn test_tags() {
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+
+        let metadata = parse_with_extras(&env, &validator, &[(b"tags", b"ai,vision,ai")]).unwrap();
+        let tags = metadata.tags(&env);
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags.get_unchecked(0), Bytes::from_slice(&env, b"ai"));
+        assert_eq!(tags.get_unchecked(1), Bytes::from_slice(&env, b"vision"));
+
+        let limited = MetadataValidator::new().with_parser_config(ParserConfig::new().max_tags(2));
+        assert!(parse_with_extras(&env, &limited, &[(b"tags", b"ai,vision,ai")]).is_ok());
+        assert_eq!(
+            parse_with_extras(&env, &limited, &[(b"tags", b"ai,vision,nlp")]),
+            Err(MetadataError::InvalidLength)
+        );
+
+        let strict = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().reject_duplicate_tags(true)
+        );
+        assert_eq!(
+            parse_with_extras(&env, &strict, &[(b"tags", b"ai,vision,ai")]),
+            Err(MetadataError::InvalidStructure)
+        );
+    }
+
+    #[test]
+    fn test_forbid_prerelease() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().forbid_prerelease(true)
+        );
+
+        let parse = |version: &[u8]| {
+            let mut input = sample_input(&env);
+            input.version = Bytes::from_slice(&env, version);
+            validator.validate_input(&env, input)
+        };
+
+        assert!(parse(b"1.0.0").is_ok());
+        assert_eq!(parse(b"1.0.0-beta"), Err(MetadataError::InvalidStructure));
+
+        let mut input = sample_input(&env);
+        input.version = Bytes::from_slice(&env, b"1.0.0-beta");
+        assert!(MetadataValidator::new().validate_input(&env, input).is_ok());
+    }
+
+    #[test]
+    fn test_get_extra_or() {
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+        let metadata = parse_with_extras(&env, &validator, &[(b"kind", b"trader")]).unwrap();
+        let default = Bytes::from_slice(&env, b"unknown");
+
+        assert_eq!(
+            metadata.get_extra_or(&env, &Bytes::from_slice(&env, b"kind"), default.clone()),
+            Bytes::from_slice(&env, b"trader")
+        );
+        assert_eq!(
+            metadata.get_extra_or(&env, &Bytes::from_slice(&env, b"content_type"), default.clone()),
+            default
+        );
+    }
+
+    #[test]
+    fn test_allowed_cid_prefixes() {
+        let env = Env::default();
+        let mut prefixes = Vec::new(&env);
+        prefixes.push_back(Bytes::from_slice(&env, b"bafy"));
+        prefixes.push_back(Bytes::from_slice(&env, b"QmYw"));
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().with_allowed_cid_prefixes(prefixes)
+        );
+
+        let matching = Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
+        let other = Bytes::from_slice(&env, b"QmT78zSuBmuS4z925WZfrqQ1qHaJ56DQaTfyMUF7F8ff5o");
+        assert_eq!(validator.validate_cid(&env, &matching), Ok(()));
+        assert_eq!(validator.validate_cid(&env, &other), Err(MetadataError::InvalidCidFormat));
+
+        let unrestricted = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().with_allowed_cid_prefixes(Vec::new(&env))
+        );
+        assert_eq!(unrestricted.validate_cid(&env, &other), Ok(()));
+    }
+
+    #[test]
+    fn test_require_sorted_extra() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().require_sorted_extra(true)
+        );
+
+        let sorted: &[(&[u8], &[u8])] = &[(b"content_type", b"gguf"), (b"kind", b"trader")];
+        let unsorted: &[(&[u8], &[u8])] = &[(b"kind", b"trader"), (b"content_type", b"gguf")];
+        let repeated: &[(&[u8], &[u8])] = &[(b"kind", b"trader"), (b"kind", b"trader")];
+
+        assert!(parse_with_extras(&env, &validator, sorted).is_ok());
+        assert_eq!(
+            parse_with_extras(&env, &validator, unsorted),
+            Err(MetadataError::InvalidStructure)
+        );
+        assert_eq!(
+            parse_with_extras(&env, &validator, repeated),
+            Err(MetadataError::InvalidStructure)
+        );
+        assert!(parse_with_extras(&env, &MetadataValidator::new(), unsorted).is_ok());
+    }
+
+    #[test]
+    fn test_to_kv() {
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+        let metadata = parse_with_extras(
+            &env,
+            &validator,
+            &[(b"kind", b"trader"), (b"content_type", b"gguf")]
+        ).unwrap();
+
+        let kv = metadata.to_kv(&env);
+        let entry = |key: &[u8], value: &Bytes| (Bytes::from_slice(&env, key), value.clone());
+
+        assert_eq!(kv.len(), 7);
+        assert_eq!(kv.get_unchecked(0), entry(b"json_cid", &metadata.json_cid));
+        assert_eq!(kv.get_unchecked(1), entry(b"model_hash", &metadata.model_hash));
+        assert_eq!(kv.get_unchecked(2), entry(b"name", &metadata.name));
+        assert_eq!(kv.get_unchecked(3), entry(b"description", &metadata.description));
+        assert_eq!(kv.get_unchecked(4), entry(b"version", &metadata.version));
+        assert_eq!(kv.slice(5..), metadata.extra_fields);
+    }
+
+    #[test]
+    fn test_model_size() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().validate_model_size(true).max_model_size(1_000_000)
+        );
+
+        let metadata = parse_with_extras(
+            &env,
+            &validator,
+            &[(b"model_size_bytes", b"524288")]
+        ).unwrap();
+        assert_eq!(metadata.model_size(&env), Some(524_288));
+
+        for value in [b"0".as_slice(), b"1000001", b"12MB"] {
+            assert_eq!(
+                parse_with_extras(&env, &validator, &[(b"model_size_bytes", value)]),
+                Err(MetadataError::InvalidStructure)
+            );
+        }
+
+        let without = parse_with_extras(&env, &validator, &[]).unwrap();
+        assert_eq!(without.model_size(&env), None);
+
+        // Unchecked by default
+        let unchecked = MetadataValidator::new();
+        assert!(parse_with_extras(&env, &unchecked, &[(b"model_size_bytes", b"12MB")]).is_ok());
+    }
+
+    #[test]
+    fn test_validate_and_receipt() {
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+        let signer_key = Bytes::from_array(&env, &[7u8; 32]);
+
+        let (metadata, receipt) = validator
+            .validate_and_receipt(&env, sample_input(&env), &signer_key)
+            .unwrap();
+
+        assert_eq!(receipt, metadata.fingerprint(&env));
+
+        let mut bad = sample_input(&env);
+        bad.json_cid = Bytes::from_slice(&env, b"bad");
+        assert_eq!(
+            validator.validate_and_receipt(&env, bad, &signer_key),
+            Err(MetadataError::InvalidCidFormat)
+        );
+    }
+
+    #[test]
+    fn test_require_distinct_name_description() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().require_distinct_name_description(true)
+        );
+
+        assert!(validator.validate_input(&env, sample_input(&env)).is_ok());
+
+        let mut copied = sample_input(&env);
+        copied.description = copied.name.clone();
+        assert_eq!(
+            validator.validate_input(&env, copied.clone()),
+            Err(MetadataError::InvalidStructure)
+        );
+        assert!(MetadataValidator::new().validate_input(&env, copied).is_ok());
+    }
+
+    #[test]
+    fn test_fits_entry_limit() {
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+        let metadata = validator.validate_input(&env, sample_input(&env)).unwrap();
+        assert!(metadata.fits_entry_limit());
+
+        let mut oversized = metadata.clone();
+        oversized.extra_fields.push_back((
+            Bytes::from_slice(&env, b"blob"),
+            Bytes::from_slice(&env, &[b'x'; storage::MAX_ENTRY_SIZE as usize])
+        ));
+        assert!(!oversized.fits_entry_limit());
+    }
+
+    #[test]
+    fn test_validate_and_normalize_batch() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().lowercase_hash(true)
+        );
+
+        let lower = sample_input(&env);
+        let mut upper = sample_input(&env);
+        upper.model_hash = Bytes::from_slice(&env, b"A1B2C3D4E5F6789012345678901234567890ABCDEF");
+
+        let mut items = Vec::new(&env);
+        items.push_back(lower.clone());
+        items.push_back(upper);
+
+        let normalized = validator.validate_and_normalize_batch(&env, items.clone()).unwrap();
+        assert_eq!(normalized.len(), 2);
+        for metadata in normalized.iter() {
+            assert_eq!(metadata.model_hash, lower.model_hash);
+        }
+
+        let mut bad = sample_input(&env);
+        bad.json_cid = Bytes::from_slice(&env, b"bad");
+        items.push_back(bad);
+        assert_eq!(
+            validator.validate_and_normalize_batch(&env, items),
+            Err((2, MetadataError::InvalidCidFormat))
+        );
+    }
+
+    #[test]
+    fn test_is_deprecated() {
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+        let strict = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().strict_deprecated(true)
+        );
+
+        let deprecated = parse_with_extras(&env, &strict, &[(b"deprecated", b"true")]).unwrap();
+        assert!(deprecated.is_deprecated(&env));
+
+        let active = parse_with_extras(&env, &strict, &[(b"deprecated", b"false")]).unwrap();
+        assert!(!active.is_deprecated(&env));
+
+        let absent = parse_with_extras(&env, &strict, &[]).unwrap();
+        assert!(!absent.is_deprecated(&env));
+
+        let garbage = parse_with_extras(&env, &validator, &[(b"deprecated", b"yes")]).unwrap();
+        assert!(!garbage.is_deprecated(&env));
+        assert_eq!(
+            parse_with_extras(&env, &strict, &[(b"deprecated", b"yes")]),
+            Err(MetadataError::InvalidStructure)
+        );
+    }
+
+    #[test]
+    fn test_require_extra_fields() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().require_extra_fields(true)
+        );
+
+        assert!(parse_with_extras(&env, &validator, &[(b"license", b"MIT")]).is_ok());
+        assert_eq!(
+            parse_with_extras(&env, &validator, &[]),
+            Err(MetadataError::MissingRequiredField)
+        );
+    }
+
+    #[test]
+    fn test_validate_with_progress() {
+        use core::sync::atomic::{ AtomicU32, Ordering };
+
+        // A `fn` pointer cannot capture, so the sequence is packed into a
+        // static: one nibble per field, in call order
+        static SEQUENCE: AtomicU32 = AtomicU32::new(0);
+        fn record(field: FieldId) {
+            let code = field as u32 + 1;
+            let _ = SEQUENCE.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |seq| {
+                Some((seq << 4) | code)
+            });
+        }
+
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+
+        validator.validate_with_progress(&env, sample_input(&env), record).unwrap();
+        assert_eq!(SEQUENCE.swap(0, Ordering::SeqCst), 0x123456);
+
+        let mut bad_name = sample_input(&env);
+        bad_name.name = Bytes::new(&env);
+        assert_eq!(
+            validator.validate_with_progress(&env, bad_name, record),
+            Err(MetadataError::MissingRequiredField)
+        );
+        assert_eq!(SEQUENCE.swap(0, Ordering::SeqCst), 0x12);
+
+        // A name failing a later policy check is not reported as passed
+        let dns = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().name_must_be_dns_label(true)
+        );
+        let mut spaced = sample_input(&env);
+        spaced.name = Bytes::from_slice(&env, b"Test Agent");
+        assert_eq!(
+            dns.validate_with_progress(&env, spaced, record),
+            Err(MetadataError::InvalidStructure)
+        );
+        assert_eq!(SEQUENCE.load(Ordering::SeqCst), 0x12);
+    }
+
+    #[test]
+    fn test_migrate() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().lowercase_hash(true)
+        );
+
+        env.as_contract(&contract_id, || {
+            let agent_id = Bytes::from_slice(&env, b"agent-1");
+            let mut input = sample_input(&env);
+            input.model_hash = Bytes::from_slice(
+                &env,
+                b"A1B2C3D4E5F6789012345678901234567890ABCDEF"
+            );
+            let legacy = MetadataValidator::new().validate_input(&env, input).unwrap();
+            storage::store(&env, &agent_id, &legacy);
+
+            assert_eq!(validator.migrate(&env, &agent_id), Ok(true));
+            let migrated = storage::load(&env, &agent_id).unwrap();
+            assert_eq!(migrated.model_hash, sample_input(&env).model_hash);
+
+            assert_eq!(validator.migrate(&env, &agent_id), Ok(false));
+            assert_eq!(
+                validator.migrate(&env, &Bytes::from_slice(&env, b"missing")),
+                Err(MetadataError::MissingRequiredField)
+            );
+        });
+    }
+
+    #[test]
+    fn test_min_distinct_tags() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().min_distinct_tags(2)
+        );
+
+        assert!(parse_with_extras(&env, &validator, &[(b"tags", b"ai,vision")]).is_ok());
+        assert_eq!(
+            parse_with_extras(&env, &validator, &[(b"tags", b"ai,ai")]),
+            Err(MetadataError::MissingRequiredField)
+        );
+        assert_eq!(
+            parse_with_extras(&env, &validator, &[]),
+            Err(MetadataError::MissingRequiredField)
+        );
+    }
+
+    #[test]
+    fn test_partition_extra() {
+        let env = Env::default();
+        let mut allowed_keys = Vec::new(&env);
+        allowed_keys.push_back(Bytes::from_slice(&env, b"license"));
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().with_allowed_extra_keys(allowed_keys)
+        );
+
+        let license = (Bytes::from_slice(&env, b"license"), Bytes::from_slice(&env, b"MIT"));
+        let promo = (Bytes::from_slice(&env, b"promo"), Bytes::from_slice(&env, b"buy now"));
+        let mut input = sample_input(&env);
+        input.extra_fields.push_back(license.clone());
+        input.extra_fields.push_back(promo.clone());
+
+        assert_eq!(
+            validator.validate_input(&env, input.clone()),
+            Err(MetadataError::DisallowedExtraField)
+        );
+
+        let (metadata, unknown) = validator.partition_extra(&env, input).unwrap();
+        assert_eq!(metadata.extra_fields, Vec::from_array(&env, [license]));
+        assert_eq!(unknown, Vec::from_array(&env, [promo]));
+    }
+
+    #[test]
+    fn test_verify_cid_binds_hash() {
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+
+        let cid = Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
+        // The sha2-256 digest carried in the CID's multihash
+        let bound = Bytes::from_slice(
+            &env,
+            b"9d6c2be50f706953479ab9df2ce3edca90b68053c00b3004b7f0accbe1e8eedf"
+        );
+        assert_eq!(validator.verify_cid_binds_hash(&env, &cid, &bound), Ok(()));
+
+        let other = Bytes::from_slice(
+            &env,
+            b"d4f0bc5a29de06b510f9aa428f1eedba926012b591fef7a518e776a7c9bd1824"
+        );
+        assert_eq!(
+            validator.verify_cid_binds_hash(&env, &cid, &other),
+            Err(MetadataError::HashVerificationFailed)
+        );
+    }
+
+    #[test]
+    fn test_independent_name_and_description_caps() {
+        let env = Env::default();
+        let name_capped = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().max_name_length(5).max_description_length(20)
+        );
+        let description_capped = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().max_name_length(20).max_description_length(5)
+        );
+
+        // "TestAgent" is 9 bytes and "A test agent" 12
+        assert_eq!(
+            name_capped.validate_input(&env, sample_input(&env)),
+            Err(MetadataError::InvalidLength)
+        );
+        assert_eq!(
+            description_capped.validate_input(&env, sample_input(&env)),
+            Err(MetadataError::InvalidLength)
+        );
+
+        let both_fit = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().max_name_length(9).max_description_length(12)
+        );
+        assert!(both_fit.validate_input(&env, sample_input(&env)).is_ok());
+    }
+
+    #[test]
+    fn test_api_codes_are_frozen() {
+        let codes = [
+            (MetadataError::InvalidJsonFormat, 2001),
+            (MetadataError::MissingRequiredField, 2002),
+            (MetadataError::InvalidCidFormat, 2003),
+            (MetadataError::HashVerificationFailed, 2004),
+            (MetadataError::InvalidStructure, 2005),
+            (MetadataError::CidTooLong, 2006),
+            (MetadataError::HashTooLong, 2007),
+            (MetadataError::InvalidLength, 2008),
+            (MetadataError::DuplicateExtraField, 2009),
+            (MetadataError::NameTaken, 2010),
+            (MetadataError::DisallowedExtraField, 2011),
+        ];
+
+        for (error, code) in codes {
+            assert_eq!(error.api_code(), code);
+        }
+    }
+
+    #[test]
+    fn test_forbid_redundant_asset_cid() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().forbid_redundant_asset_cid(true)
+        );
+
+        let distinct: &[u8] = b"QmT78zSuBmuS4z925WZfrqQ1qHaJ56DQaTfyMUF7F8ff5o";
+        let redundant: &[u8] = b"QmT78zSuBmuS4z925WZfrqQ1qHaJ56DQaTfyMUF7F8ff5o,\
+            QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+
+        assert!(parse_with_extras(&env, &validator, &[(b"assets", distinct)]).is_ok());
+        assert_eq!(
+            parse_with_extras(&env, &validator, &[(b"assets", redundant)]),
+            Err(MetadataError::DuplicateExtraField)
+        );
+    }
+
+    #[test]
+    fn test_register() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        let validator = MetadataValidator::new();
+
+        env.as_contract(&contract_id, || {
+            let agent_id = Bytes::from_slice(&env, b"agent-1");
+
+            let fingerprint = validator.register(&env, &agent_id, sample_input(&env)).unwrap();
+            let stored = storage::load(&env, &agent_id).unwrap();
+            assert_eq!(fingerprint, stored.fingerprint(&env));
+            assert_eq!(env.events().all().len(), 1);
+
+            let mut bad = sample_input(&env);
+            bad.json_cid = Bytes::from_slice(&env, b"bad");
+            let other_id = Bytes::from_slice(&env, b"agent-2");
+            assert_eq!(
+                validator.register(&env, &other_id, bad),
+                Err(MetadataError::InvalidCidFormat)
+            );
+            assert_eq!(storage::load(&env, &other_id), None);
+        });
+    }
+
+    #[test]
+    fn test_list_agents() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        let validator = MetadataValidator::new();
+
+        env.as_contract(&contract_id, || {
+            let ids = [b"agent-1".as_slice(), b"agent-2", b"agent-3"];
+            for id in ids {
+                let agent_id = Bytes::from_slice(&env, id);
+                validator.register(&env, &agent_id, sample_input(&env)).unwrap();
+            }
+
+            let listed = MetadataValidator::list_agents(&env);
+            assert_eq!(listed.len(), 3);
+            for id in ids {
+                assert!(listed.contains(&Bytes::from_slice(&env, id)));
+            }
+        });
+    }
+
+    #[test]
+    fn test_remove() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        let validator = MetadataValidator::new();
+
+        env.as_contract(&contract_id, || {
+            let first = Bytes::from_slice(&env, b"agent-1");
+            let second = Bytes::from_slice(&env, b"agent-2");
+            validator.register(&env, &first, sample_input(&env)).unwrap();
+            validator.register(&env, &second, sample_input(&env)).unwrap();
+
+            assert_eq!(validator.remove(&env, &first), Ok(()));
+            assert_eq!(storage::load(&env, &first), None);
+            assert_eq!(MetadataValidator::list_agents(&env), Vec::from_array(&env, [second]));
+
+            assert_eq!(validator.remove(&env, &first), Err(MetadataError::MissingRequiredField));
+            assert_eq!(
+                validator.remove(&env, &Bytes::from_slice(&env, b"never-stored")),
+                Err(MetadataError::MissingRequiredField)
+            );
+        });
+    }
+
+    #[test]
+    fn test_rank() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().validate_rank(true)
+        );
+
+        let ranked = parse_with_extras(&env, &validator, &[(b"rank", b"42")]).unwrap();
+        assert_eq!(ranked.rank(&env), Some(42));
+
+        for value in [b"0".as_slice(), b"101", b"top"] {
+            assert_eq!(
+                parse_with_extras(&env, &validator, &[(b"rank", value)]),
+                Err(MetadataError::InvalidStructure)
+            );
+        }
+
+        let wide = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().validate_rank(true).rank_range(0, 1000)
+        );
+        assert!(parse_with_extras(&env, &wide, &[(b"rank", b"0")]).is_ok());
+        assert!(parse_with_extras(&env, &wide, &[(b"rank", b"101")]).is_ok());
+
+        // Unchecked by default, so free-form ranks still parse
+        let free_form = parse_with_extras(&env, &MetadataValidator::new(), &[(b"rank", b"top")]);
+        assert_eq!(free_form.unwrap().rank(&env), None);
+    }
+
+    #[test]
+    fn test_eq_unordered() {
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+
+        let a = parse_with_extras(
+            &env,
+            &validator,
+            &[(b"kind", b"trader"), (b"license", b"MIT"), (b"kind", b"trader")]
+        ).unwrap();
+        let b = parse_with_extras(
+            &env,
+            &validator,
+            &[(b"kind", b"trader"), (b"kind", b"trader"), (b"license", b"MIT")]
+        ).unwrap();
+        assert_ne!(a, b);
+        assert!(a.eq_unordered(&env, &b));
+
+        // Same distinct pairs, different multiplicity
+        let c = parse_with_extras(
+            &env,
+            &validator,
+            &[(b"kind", b"trader"), (b"license", b"MIT"), (b"license", b"MIT")]
+        ).unwrap();
+        assert!(!a.eq_unordered(&env, &c));
+
+        let mut d = b.clone();
+        d.version = Bytes::from_slice(&env, b"2.0.0");
+        assert!(!a.eq_unordered(&env, &d));
+    }
+
+    #[test]
+    fn test_reject_leading_zeros() {
+        let env = Env::default();
+        let lenient = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().validate_rank(true)
+        );
+        let strict = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().validate_rank(true).reject_leading_zeros(true)
+        );
+
+        let padded = parse_with_extras(&env, &lenient, &[(b"rank", b"007")]);
+        assert_eq!(padded.unwrap().rank(&env), Some(7));
+        assert_eq!(
+            parse_with_extras(&env, &strict, &[(b"rank", b"007")]),
+            Err(MetadataError::InvalidStructure)
+        );
+        assert!(parse_with_extras(&env, &strict, &[(b"rank", b"7")]).is_ok());
+    }
+
+    #[test]
+    fn test_ipfs_gateway_preset() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::ipfs_gateway(&env)
+        );
+
+        let dag_pb = Bytes::from_slice(
+            &env,
+            b"bafybeie5nqv6kd3qnfjupgvz34woh3oksc3iau6abmyajn7qvtf6d2ho34"
+        );
+        assert_eq!(validator.validate_cid(&env, &dag_pb), Ok(()));
+
+        // dag-cbor codec (0x71), sha2-256, 32-byte digest
+        let mut binary = Bytes::from_array(&env, &[0x01, 0x71, 0x12, 0x20]);
+        binary.extend_from_slice(&[0x42; 32]);
+        let mut dag_cbor = Bytes::from_slice(&env, b"b");
+        dag_cbor.append(&cid::base32_encode(&env, &binary));
+        assert_eq!(validator.validate_cid(&env, &dag_cbor), Err(MetadataError::InvalidCidFormat));
+
+        let v0 = Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
+        assert_eq!(validator.validate_cid(&env, &v0), Err(MetadataError::InvalidCidFormat));
+
+        let cbor_only = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().with_allowed_cid_codecs(Vec::from_array(&env, [0x71]))
+        );
+        assert_eq!(cbor_only.validate_cid(&env, &dag_cbor), Ok(()));
+        assert_eq!(cbor_only.validate_cid(&env, &dag_pb), Err(MetadataError::InvalidCidFormat));
+    }
+
+    #[test]
+    fn test_compute_hash_chunked() {
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+        let whole = Bytes::from_slice(&env, b"model weights split across chunks");
+
+        let mut chunks = Vec::new(&env);
+        chunks.push_back(whole.slice(0..5));
+        chunks.push_back(Bytes::new(&env));
+        chunks.push_back(whole.slice(5..20));
+        chunks.push_back(whole.slice(20..));
+
+        for algo in [HashAlgo::Sha256, HashAlgo::Keccak256] {
+            assert_eq!(
+                validator.compute_hash_chunked(&env, chunks.clone(), algo),
+                hash::digest(&env, algo, &whole)
+            );
+        }
+        assert_eq!(
+            validator.compute_hash_chunked(&env, chunks, HashAlgo::Sha256),
+            Bytes::from_array(&env, &env.crypto().sha256(&whole).to_array())
+        );
+    }
+
+    #[test]
+    fn test_forbid_empty_extra_values() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().forbid_empty_extra_values(true)
+        );
+
+        assert_eq!(
+            parse_with_extras(&env, &validator, &[(b"verified", b"")]),
+            Err(MetadataError::MissingRequiredField)
+        );
+        assert!(parse_with_extras(&env, &validator, &[(b"verified", b"yes")]).is_ok());
+        assert!(parse_with_extras(&env, &MetadataValidator::new(), &[(b"verified", b"")]).is_ok());
+    }
+
+    #[test]
+    fn test_store_raw_hash() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().store_raw_hash(true)
+        );
+        let hex = Bytes::from_slice(
+            &env,
+            b"d4f0bc5a29de06b510f9aa428f1eedba926012b591fef7a518e776a7c9bd1824"
+        );
+
+        let mut input = sample_input(&env);
+        input.model_hash = hex.clone();
+        let metadata = validator.validate_input(&env, input).unwrap();
+
+        assert_eq!(metadata.model_hash.len(), 32);
+        assert_eq!(Some(metadata.model_hash), hash::decode_hex(&env, &hex));
+    }
+
+    #[test]
+    fn test_batch_encoding_report() {
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+
+        let v0_hex = sample_input(&env);
+        let mut v1_base58 = sample_input(&env);
+        v1_base58.json_cid = Bytes::from_slice(
+            &env,
+            b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
+        );
+        v1_base58.model_hash = Bytes::from_slice(
+            &env,
+            b"FLELiEbZiFHjRSx9uZssckQd8RtPu3nLCKFB1gTuXnNX"
+        );
+        let mut garbage = sample_input(&env);
+        garbage.json_cid = Bytes::from_slice(&env, b"not-a-cid");
+
+        let items = Vec::from_array(&env, [v0_hex.clone(), v1_base58, garbage, v0_hex]);
+        let report = validator.batch_encoding_report(&env, items);
+
+        assert_eq!(
+            report,
+            EncodingReport {
+                cid_v0: 2,
+                cid_v1: 1,
+                cid_unknown: 1,
+                hash_hex: 3,
+                hash_base58: 1,
+                hash_unknown: 0,
+            }
+        );
+        assert!(!report.is_consistent());
+    }
+
+    #[test]
+    fn test_forbid_zero_version() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().forbid_zero_version(true)
+        );
+
+        let parse = |validator: &MetadataValidator, version: &[u8]| {
+            let mut input = sample_input(&env);
+            input.version = Bytes::from_slice(&env, version);
+            validator.validate_input(&env, input)
+        };
+
+        assert_eq!(parse(&validator, b"0.0.0"), Err(MetadataError::InvalidStructure));
+        assert!(parse(&validator, b"0.1.0").is_ok());
+        assert!(parse(&MetadataValidator::new(), b"0.0.0").is_ok());
     }
 
-    /// Validate only CID and hash (for quick checks)
-    pub fn validate_cid_and_hash(
-        env: &Env,
-        json_cid: Bytes,
-        model_hash: Bytes
-    ) -> Result<(), MetadataError> {
+    #[test]
+    fn test_identicon_seed() {
+        let env = Env::default();
         let validator = MetadataValidator::new();
-        validator.validate_cid(env, &json_cid)?;
-        validator.validate_model_hash(env, &model_hash)?;
-        Ok(())
+        let metadata = validator.validate_input(&env, sample_input(&env)).unwrap();
+
+        let mut expected = [0u8; 8];
+        metadata.fingerprint(&env).slice(0..8).copy_into_slice(&mut expected);
+        assert_eq!(metadata.identicon_seed(&env), u64::from_be_bytes(expected));
+        assert_eq!(metadata.clone().identicon_seed(&env), metadata.identicon_seed(&env));
+
+        let mut other = metadata.clone();
+        other.version = Bytes::from_slice(&env, b"1.0.1");
+        assert_ne!(other.identicon_seed(&env), metadata.identicon_seed(&env));
     }
 
-    /// Validate with custom configurations
-    pub fn validate_with_custom_config(
-        env: &Env,
-        json_cid: Bytes,
-        model_hash: Bytes,
-        name: Bytes,
-        description: Bytes,
-        version: Bytes,
-        extra_fields: Vec<(Bytes, Bytes)>,
-        cid_config: ValidatorConfig,
-        hash_config: ValidatorConfig
-    ) -> Result<AgentMetadata, MetadataError> {
-        let validator = MetadataValidator::with_config(
-            cid_config,
-            hash_config,
-            ValidatorConfig::new().with_length_bounds(1, 100),
-            ValidatorConfig::new().with_length_bounds(1, 1000),
-            ValidatorConfig::new().with_length_bounds(1, 50)
+    #[test]
+    fn test_forbid_scalar_key_shadowing() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().forbid_scalar_key_shadowing(true)
         );
 
-        validator.validate_and_parse(
-            env,
-            json_cid,
-            model_hash,
-            name,
-            description,
-            version,
-            extra_fields
-        )
+        assert_eq!(
+            parse_with_extras(&env, &validator, &[(b"name", b"Impostor")]),
+            Err(MetadataError::DisallowedExtraField)
+        );
+        assert!(parse_with_extras(&env, &validator, &[(b"nickname", b"Agent")]).is_ok());
+        let permissive = MetadataValidator::new();
+        assert!(parse_with_extras(&env, &permissive, &[(b"name", b"Impostor")]).is_ok());
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use soroban_sdk::{ Bytes, Env, Vec };
 
     #[test]
-    fn test_valid_cid_validation() {
+    fn test_rekey_extra() {
         let env = Env::default();
-        let validator = CIDValidator::new();
-
-        // Test valid CID formats
-        let valid_cid_v0 = Bytes::from_slice(
+        let validator = MetadataValidator::new();
+        let map = Vec::from_array(
             &env,
-            b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"
+            [(Bytes::from_slice(&env, b"url"), Bytes::from_slice(&env, b"homepage"))]
         );
-        let valid_cid_v1 = Bytes::from_slice(
+
+        let metadata = parse_with_extras(
             &env,
-            b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
+            &validator,
+            &[(b"url", b"https://example.com"), (b"kind", b"trader")]
+        ).unwrap();
+        let rekeyed = metadata.rekey_extra(&env, map.clone()).unwrap();
+        assert_eq!(
+            rekeyed.extra_fields,
+            Vec::from_array(
+                &env,
+                [
+                    (
+                        Bytes::from_slice(&env, b"homepage"),
+                        Bytes::from_slice(&env, b"https://example.com"),
+                    ),
+                    (Bytes::from_slice(&env, b"kind"), Bytes::from_slice(&env, b"trader")),
+                ]
+            )
         );
+        assert_eq!(rekeyed.name, metadata.name);
 
-        assert!(validator.validate(&env, &valid_cid_v0).is_ok());
-        assert!(validator.validate(&env, &valid_cid_v1).is_ok());
+        let colliding = parse_with_extras(
+            &env,
+            &validator,
+            &[(b"url", b"https://example.com"), (b"homepage", b"https://example.org")]
+        ).unwrap();
+        assert_eq!(colliding.rekey_extra(&env, map), Err(MetadataError::DuplicateExtraField));
     }
 
     #[test]
-    fn test_invalid_cid_validation() {
+    fn test_max_description_words() {
         let env = Env::default();
-        let validator = CIDValidator::new();
+        let validator = |max: u32| {
+            MetadataValidator::new().with_parser_config(
+                ParserConfig::new().max_description_words(max)
+            )
+        };
+        let mut input = sample_input(&env);
+        input.description = Bytes::from_slice(&env, b"  A   test agent ");
 
-        // Test invalid CID formats
-        let empty_cid = Bytes::from_slice(&env, b"");
-        let short_cid = Bytes::from_slice(&env, b"Qm");
-        let long_cid = Bytes::from_slice(
+        assert!(validator(3).validate_input(&env, input.clone()).is_ok());
+        assert_eq!(validator(2).validate_input(&env, input), Err(MetadataError::InvalidLength));
+    }
+
+    #[test]
+    fn test_compressed_bytes_round_trip() {
+        let env = Env::default();
+        let metadata = parse_with_extras(
             &env,
-            b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdGQmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdGQmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdGQmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG"
-        );
+            &MetadataValidator::new(),
+            &[
+                (b"padding", &[b'0'; 64]),
+                (b"notes", b"aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa"),
+                (b"kind", b"trader"),
+            ]
+        ).unwrap();
 
-        assert!(validator.validate(&env, &empty_cid).is_err());
-        assert!(validator.validate(&env, &short_cid).is_err());
-        assert!(validator.validate(&env, &long_cid).is_err());
+        let compressed = metadata.to_compressed_bytes(&env);
+        assert!(compressed.len() < metadata.to_bytes(&env).len());
+        assert_eq!(AgentMetadata::from_compressed_bytes(&env, &compressed), Ok(metadata));
+
+        // Repeat control byte with nothing to repeat
+        assert_eq!(
+            AgentMetadata::from_compressed_bytes(&env, &Bytes::from_slice(&env, &[0x85])),
+            Err(MetadataError::InvalidStructure)
+        );
     }
 
     #[test]
-    fn test_valid_hash_validation() {
+    fn test_max_total_cids() {
         let env = Env::default();
-        let validator = HashValidator::new();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().max_total_cids(3)
+        );
+        let sig: &[u8] = b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
 
-        let valid_hash = Bytes::from_slice(&env, b"a1b2c3d4e5f6789012345678901234567890abcdef");
-        let short_valid_hash = Bytes::from_slice(&env, b"00000000000000000000000000000000");
+        // Primary + sig + one asset
+        let under = parse_with_extras(&env, &validator, &[(b"sig_cid", sig), (b"assets", sig)]);
+        assert!(under.is_ok());
 
-        assert!(validator.validate(&env, &valid_hash).is_ok());
-        assert!(validator.validate(&env, &short_valid_hash).is_ok());
+        let two_assets: &[u8] =
+            b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG,bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi";
+        assert_eq!(
+            parse_with_extras(&env, &validator, &[(b"sig_cid", sig), (b"assets", two_assets)]),
+            Err(MetadataError::InvalidLength)
+        );
     }
 
     #[test]
-    fn test_invalid_hash_validation() {
+    fn test_type_check_well_known() {
         let env = Env::default();
-        let validator = HashValidator::new();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().type_check_well_known(true)
+        );
 
-        let empty_hash = Bytes::from_slice(&env, b"");
-        let short_hash = Bytes::from_slice(&env, b"abc");
-        let invalid_chars = Bytes::from_slice(&env, b"xyz123");
+        let homepage: &[u8] = b"https://example.com";
+        let license: &[u8] = b"not a license!";
 
-        assert!(validator.validate(&env, &empty_hash).is_err());
-        assert!(validator.validate(&env, &short_hash).is_err());
-        // Note: The new validator doesn't check for specific character patterns unless in strict mode
-        assert!(validator.validate(&env, &invalid_chars).is_ok()); // Lenient mode allows this
+        assert!(parse_with_extras(&env, &validator, &[(b"homepage", homepage)]).is_ok());
+        assert_eq!(
+            parse_with_extras(&env, &validator, &[(b"license", license)]),
+            Err(MetadataError::InvalidStructure)
+        );
+        assert!(parse_with_extras(&env, &validator, &[(b"nickname", b"free form!")]).is_ok());
+        let permissive = MetadataValidator::new();
+        assert!(parse_with_extras(&env, &permissive, &[(b"license", license)]).is_ok());
     }
 
     #[test]
-    fn test_complete_metadata_validation() {
+    fn test_change_class() {
         let env = Env::default();
-        let validator = MetadataValidator::new();
+        let original = MetadataValidator::new().validate_input(&env, sample_input(&env)).unwrap();
 
-        let json_cid = Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
-        let model_hash = Bytes::from_slice(&env, b"a1b2c3d4e5f6789012345678901234567890abcdef");
-        let name = Bytes::from_slice(&env, b"TestAgent");
-        let description = Bytes::from_slice(&env, b"A test agent for validation");
-        let version = Bytes::from_slice(&env, b"1.0.0");
-        let extra_fields = Vec::new(&env);
+        assert_eq!(original.change_class(&original.clone()), ChangeClass::None);
 
-        let result = validator.validate_and_parse(
+        let mut reworded = original.clone();
+        reworded.description = Bytes::from_slice(&env, b"A reworded test agent");
+        assert_eq!(original.change_class(&reworded), ChangeClass::Cosmetic);
+
+        let mut repointed = original.clone();
+        repointed.json_cid = Bytes::from_slice(
             &env,
-            json_cid.clone(),
-            model_hash.clone(),
-            name.clone(),
-            description.clone(),
-            version.clone(),
-            extra_fields
+            b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
         );
+        assert_eq!(original.change_class(&repointed), ChangeClass::Material);
 
-        assert!(result.is_ok());
-        let metadata = result.unwrap();
-        assert_eq!(metadata.json_cid, json_cid);
-        assert_eq!(metadata.model_hash, model_hash);
-        assert_eq!(metadata.name, name);
-        assert_eq!(metadata.description, description);
-        assert_eq!(metadata.version, version);
+        repointed.description = reworded.description;
+        assert_eq!(original.change_class(&repointed), ChangeClass::Material);
+
+        let mut renamed = original.clone();
+        renamed.name = Bytes::from_slice(&env, b"RenamedAgent");
+        assert_eq!(original.change_class(&renamed), ChangeClass::Material);
     }
 
     #[test]
-    fn test_missing_required_fields() {
+    fn test_require_spdx_license() {
         let env = Env::default();
-        let validator = MetadataValidator::new();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().require_spdx_license(true)
+        );
 
-        let json_cid = Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
-        let model_hash = Bytes::from_slice(&env, b"a1b2c3d4e5f6789012345678901234567890abcdef");
-        let empty_name = Bytes::from_slice(&env, b"");
-        let description = Bytes::from_slice(&env, b"A test agent");
-        let version = Bytes::from_slice(&env, b"1.0.0");
-        let extra_fields = Vec::new(&env);
+        assert!(parse_with_extras(&env, &validator, &[(b"license", b"MIT")]).is_ok());
+        assert_eq!(
+            parse_with_extras(&env, &validator, &[(b"license", b"not a license!")]),
+            Err(MetadataError::InvalidStructure)
+        );
+        assert!(parse_with_extras(&env, &validator, &[]).is_ok());
+    }
 
-        let result = validator.validate_and_parse(
+    #[test]
+    fn test_search_projection() {
+        let env = Env::default();
+        let metadata = parse_with_extras(
             &env,
-            json_cid,
-            model_hash,
-            empty_name,
-            description,
-            version,
-            extra_fields
+            &MetadataValidator::new(),
+            &[(b"tags", b"defi,arbitrage"), (b"kind", b"trader")]
+        ).unwrap();
+
+        let mut reader = encoding::Reader::new(metadata.search_projection(&env));
+        assert_eq!(reader.read_field(), Ok(metadata.name.clone()));
+        assert_eq!(reader.read_field(), Ok(metadata.version.clone()));
+        assert_eq!(reader.read_field(), Ok(metadata.description.clone()));
+        assert_eq!(reader.read_u32(), Ok(2));
+        assert_eq!(reader.read_field(), Ok(Bytes::from_slice(&env, b"defi")));
+        assert_eq!(reader.read_field(), Ok(Bytes::from_slice(&env, b"arbitrage")));
+        // Nothing else: no CID, model hash or other extras
+        assert_eq!(reader.finish(), Ok(()));
+    }
+
+    #[test]
+    fn test_forbid_numeric_name() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().forbid_numeric_name(true)
         );
+        let with_name = |name: &[u8]| {
+            let mut input = sample_input(&env);
+            input.name = Bytes::from_slice(&env, name);
+            input
+        };
 
-        assert_eq!(result, Err(MetadataError::MissingRequiredField));
+        assert_eq!(
+            validator.validate_input(&env, with_name(b"12345")),
+            Err(MetadataError::InvalidStructure)
+        );
+        assert!(validator.validate_input(&env, with_name(b"agent12")).is_ok());
+        assert!(MetadataValidator::new().validate_input(&env, with_name(b"12345")).is_ok());
     }
 
     #[test]
-    fn test_convenience_functions() {
+    fn test_require_assets_with_model_size() {
         let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().require_assets_with_model_size(true)
+        );
+        let asset: &[u8] = b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
 
-        let json_cid = Bytes::from_slice(&env, b"QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG");
-        let model_hash = Bytes::from_slice(&env, b"a1b2c3d4e5f6789012345678901234567890abcdef");
-        let name = Bytes::from_slice(&env, b"TestAgent");
-        let description = Bytes::from_slice(&env, b"A test agent");
-        let version = Bytes::from_slice(&env, b"1.0.0");
+        assert!(
+            parse_with_extras(
+                &env,
+                &validator,
+                &[(b"model_size_bytes", b"1048576"), (b"assets", asset)]
+            ).is_ok()
+        );
+        assert_eq!(
+            parse_with_extras(&env, &validator, &[(b"model_size_bytes", b"1048576")]),
+            Err(MetadataError::InvalidStructure)
+        );
+        assert!(parse_with_extras(&env, &validator, &[]).is_ok());
+    }
 
-        let result = convenience::validate_metadata_quick(
+    #[test]
+    fn test_validate_for_ledger() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().strict_cid_protocol_version(23)
+        );
+        let v0 = sample_input(&env);
+        let mut v1 = sample_input(&env);
+        v1.json_cid = Bytes::from_slice(
             &env,
-            json_cid,
-            model_hash,
-            name,
-            description,
-            version
+            b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi"
         );
 
-        assert!(result.is_ok());
+        env.ledger().set_protocol_version(22);
+        assert!(validator.validate_for_ledger(&env, v0.clone()).is_ok());
+        assert!(validator.validate_for_ledger(&env, v1.clone()).is_ok());
+
+        env.ledger().set_protocol_version(23);
+        assert_eq!(
+            validator.validate_for_ledger(&env, v0),
+            Err(MetadataError::InvalidCidFormat)
+        );
+        assert!(validator.validate_for_ledger(&env, v1).is_ok());
+
+        // A multibase prefix alone does not make a CIDv1
+        let mut bogus = sample_input(&env);
+        bogus.json_cid = Bytes::from_slice(&env, b"kbogusbase36text");
+        assert_eq!(
+            validator.validate_for_ledger(&env, bogus),
+            Err(MetadataError::InvalidCidFormat)
+        );
     }
 
     #[test]
-    fn test_custom_validator_configurations() {
+    fn test_min_cid_version_decodes_cid() {
         let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().min_cid_version(1)
+        );
+        let with_cid = |cid: &[u8]| {
+            let mut input = sample_input(&env);
+            input.json_cid = Bytes::from_slice(&env, cid);
+            input
+        };
 
-        // Test with custom CID configuration
-        let cid_config = ValidatorConfig::new()
-            .with_length_bounds(5, 150) // More lenient bounds
-            .strict(false);
+        let v1 = with_cid(b"bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi");
+        assert!(validator.validate_input(&env, v1).is_ok());
+        assert_eq!(
+            validator.validate_input(&env, with_cid(b"kbogusbase36text")),
+            Err(MetadataError::InvalidCidFormat)
+        );
+        assert_eq!(
+            validator.validate_input(&env, with_cid(b"bogusstringxxxx")),
+            Err(MetadataError::InvalidCidFormat)
+        );
+        let unchecked = MetadataValidator::new();
+        assert!(unchecked.validate_input(&env, with_cid(b"bogusstringxxxx")).is_ok());
+    }
 
-        let hash_config = ValidatorConfig::new()
-            .with_length_bounds(20, 200) // More lenient bounds
-            .strict(false);
+    #[test]
+    fn test_ascii_only_name() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().require_printable_text(true).ascii_only_name(true)
+        );
+        let with_name = |name: &[u8]| {
+            let mut input = sample_input(&env);
+            input.name = Bytes::from_slice(&env, name);
+            input
+        };
 
-        let json_cid = Bytes::from_slice(&env, b"short"); // Would normally fail
-        let model_hash = Bytes::from_slice(&env, b"short_hash"); // Would normally fail
-        let name = Bytes::from_slice(&env, b"TestAgent");
-        let description = Bytes::from_slice(&env, b"A test agent");
-        let version = Bytes::from_slice(&env, b"1.0.0");
-        let extra_fields = Vec::new(&env);
+        assert!(validator.validate_input(&env, with_name(b"TradingAgent")).is_ok());
+        // "Tr" + Cyrillic small a (U+0430) + "dingAgent"
+        let lookalike = with_name("Tr\u{430}dingAgent".as_bytes());
+        assert_eq!(validator.validate_input(&env, lookalike), Err(MetadataError::InvalidStructure));
+    }
 
-        let result = convenience::validate_with_custom_config(
-            &env,
-            json_cid,
-            model_hash,
-            name,
-            description,
-            version,
-            extra_fields,
-            cid_config,
-            hash_config
+    #[test]
+    fn test_verify_fingerprints() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+        let validator = MetadataValidator::new();
+
+        env.as_contract(&contract_id, || {
+            let intact = Bytes::from_slice(&env, b"agent-1");
+            let tampered = Bytes::from_slice(&env, b"agent-2");
+            let intact_fp = validator.register(&env, &intact, sample_input(&env)).unwrap();
+            let tampered_fp = validator.register(&env, &tampered, sample_input(&env)).unwrap();
+
+            let mut altered = storage::load(&env, &tampered).unwrap();
+            altered.description = Bytes::from_slice(&env, b"Quietly changed");
+            storage::store(&env, &tampered, &altered);
+
+            let expected = Vec::from_array(
+                &env,
+                [(intact.clone(), intact_fp), (tampered.clone(), tampered_fp.clone())]
+            );
+            assert_eq!(
+                validator.verify_fingerprints(&env, expected),
+                Vec::from_array(&env, [tampered.clone()])
+            );
+
+            let missing = Bytes::from_slice(&env, b"agent-3");
+            let expected = Vec::from_array(&env, [(missing.clone(), tampered_fp)]);
+            assert_eq!(
+                validator.verify_fingerprints(&env, expected),
+                Vec::from_array(&env, [missing])
+            );
+        });
+    }
+
+    #[test]
+    fn test_maintainers() {
+        let env = Env::default();
+        let validator = MetadataValidator::new().with_parser_config(
+            ParserConfig::new().validate_maintainers(true).max_maintainers(2)
         );
+        let two_keys: &[u8] = b"\
+            d4f0bc5a29de06b510f9aa428f1eedba926012b591fef7a518e776a7c9bd1824,\
+            9d6c2be50f706953479ab9df2ce3edca90b68053c00b3004b7f0accbe1e8eedf";
 
-        assert!(result.is_ok());
+        let metadata = parse_with_extras(&env, &validator, &[(b"maintainers", two_keys)]).unwrap();
+        let maintainers = metadata.maintainers(&env);
+        assert_eq!(maintainers.len(), 2);
+        assert_eq!(
+            maintainers.get(1),
+            Some(Bytes::from_slice(
+                &env,
+                b"9d6c2be50f706953479ab9df2ce3edca90b68053c00b3004b7f0accbe1e8eedf"
+            ))
+        );
+
+        // Second key is one byte short
+        let malformed: &[u8] = b"\
+            d4f0bc5a29de06b510f9aa428f1eedba926012b591fef7a518e776a7c9bd1824,\
+            9d6c2be50f706953479ab9df2ce3edca90b68053c00b3004b7f0accbe1e8ee";
+        assert_eq!(
+            parse_with_extras(&env, &validator, &[(b"maintainers", malformed)]),
+            Err(MetadataError::InvalidStructure)
+        );
+
+        let three_keys: &[u8] = b"\
+            d4f0bc5a29de06b510f9aa428f1eedba926012b591fef7a518e776a7c9bd1824,\
+            9d6c2be50f706953479ab9df2ce3edca90b68053c00b3004b7f0accbe1e8eedf,\
+            d4f0bc5a29de06b510f9aa428f1eedba926012b591fef7a518e776a7c9bd1824";
+        assert_eq!(
+            parse_with_extras(&env, &validator, &[(b"maintainers", three_keys)]),
+            Err(MetadataError::InvalidLength)
+        );
     }
 
     #[test]
-    fn test_validator_accessors() {
+    fn test_version_scheme() {
+        let env = Env::default();
+        let with_scheme = |scheme: VersionScheme| {
+            MetadataValidator::new().with_parser_config(ParserConfig::new().version_scheme(scheme))
+        };
+        let with_version = |version: &[u8]| {
+            let mut input = sample_input(&env);
+            input.version = Bytes::from_slice(&env, version);
+            input
+        };
+
+        let calver = with_scheme(VersionScheme::CalVer);
+        assert!(calver.validate_input(&env, with_version(b"2024.06.15")).is_ok());
+        assert_eq!(
+            calver.validate_input(&env, with_version(b"1.0.0")),
+            Err(MetadataError::InvalidStructure)
+        );
+
+        let semver = with_scheme(VersionScheme::SemVer);
+        assert!(semver.validate_input(&env, with_version(b"1.0.0")).is_ok());
+        assert!(with_scheme(VersionScheme::Any).validate_input(&env, with_version(b"v1")).is_ok());
+    }
+
+    #[test]
+    fn test_validate_against_schema() {
+        let env = Env::default();
         let validator = MetadataValidator::new();
+        let schema = Schema::new(&env)
+            .field(Bytes::from_slice(&env, b"size"), ValueType::Integer, true)
+            .field(Bytes::from_slice(&env, b"license"), ValueType::Text, false);
+        let with_extras = |extras: &[(&[u8], &[u8])]| {
+            let mut input = sample_input(&env);
+            for (key, value) in extras {
+                input.extra_fields.push_back((
+                    Bytes::from_slice(&env, key),
+                    Bytes::from_slice(&env, value),
+                ));
+            }
+            input
+        };
 
-        // Test that we can access individual validators
-        let cid_validator = validator.cid_validator();
-        let hash_validator = validator.hash_validator();
-        let name_validator = validator.name_validator();
+        let sized = with_extras(&[(b"size", b"7")]);
+        assert!(validator.validate_against_schema(&env, sized, &schema).is_ok());
 
-        assert_eq!(cid_validator.name(), "CIDValidator");
-        assert_eq!(hash_validator.name(), "HashValidator");
-        assert_eq!(name_validator.name(), "BytesValidator");
+        // `size` has no built-in rule, so only the schema rejects text there
+        let mistyped = with_extras(&[(b"size", b"large")]);
+        assert!(validator.validate_input(&env, mistyped.clone()).is_ok());
+        assert_eq!(
+            validator.validate_against_schema(&env, mistyped, &schema),
+            Err(MetadataError::InvalidStructure)
+        );
+        assert_eq!(
+            validator.validate_against_schema(&env, with_extras(&[(b"license", b"MIT")]), &schema),
+            Err(MetadataError::MissingRequiredField)
+        );
+
+        let extras: &[(&[u8], &[u8])] = &[(b"size", b"7"), (b"kind", b"trader")];
+        assert_eq!(
+            validator.validate_against_schema(&env, with_extras(extras), &schema),
+            Err(MetadataError::DisallowedExtraField)
+        );
+        let open_schema = schema.clone().allow_unknown(true);
+        assert!(validator.validate_against_schema(&env, with_extras(extras), &open_schema).is_ok());
+    }
+
+    #[test]
+    fn test_footprint_delta() {
+        let env = Env::default();
+        let validator = MetadataValidator::new();
+        let small = parse_with_extras(&env, &validator, &[]).unwrap();
+        let large = parse_with_extras(&env, &validator, &[(b"license", b"MIT")]).unwrap();
+
+        let growth = small.footprint_delta(&large);
+        assert_eq!(growth, large.storage_footprint() as i64 - small.storage_footprint() as i64);
+        assert!(growth > 0);
+        assert_eq!(large.footprint_delta(&small), -growth);
+        assert_eq!(small.footprint_delta(&small), 0);
     }
 }
@@ -0,0 +1,52 @@
+//! Typed schema descriptors for extra fields.
+
+use soroban_sdk::{ contracttype, Bytes, Env, Vec };
+
+/// Value shape a schema field must have
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ValueType {
+    /// Printable, well-formed UTF-8 text
+    Text,
+    /// Decimal `u64`
+    Integer,
+    /// CID accepted by the validator's CID rules
+    Cid,
+    /// Hash accepted by the validator's model hash rules
+    Hash,
+}
+
+/// Extra field schema checked by `MetadataValidator::validate_against_schema`
+#[derive(Clone, Debug)]
+pub struct Schema {
+    /// `(key, value type, required)` per declared field
+    pub fields: Vec<(Bytes, ValueType, bool)>,
+    /// Accept extra fields the schema does not declare
+    pub allow_unknown: bool,
+}
+
+impl Schema {
+    /// Create an empty schema that rejects undeclared fields
+    pub fn new(env: &Env) -> Self {
+        Self { fields: Vec::new(env), allow_unknown: false }
+    }
+
+    /// Declare `key` with its value type and whether it must be present
+    pub fn field(mut self, key: Bytes, value_type: ValueType, required: bool) -> Self {
+        self.fields.push_back((key, value_type, required));
+        self
+    }
+
+    pub fn allow_unknown(mut self, allow: bool) -> Self {
+        self.allow_unknown = allow;
+        self
+    }
+
+    /// Declared type of `key`, if any
+    pub fn value_type(&self, key: &Bytes) -> Option<ValueType> {
+        self.fields
+            .iter()
+            .find(|(declared, _, _)| declared == key)
+            .map(|(_, value_type, _)| value_type)
+    }
+}
@@ -0,0 +1,172 @@
+//! Persistent storage layout for agent metadata.
+//!
+//! The `MetadataKey` layout is stable: downstream contracts may read these
+//! entries directly and compose their own keys in the same namespace. New
+//! variants are only ever appended.
+
+use soroban_sdk::{ contracttype, Bytes, Env, Vec };
+
+use crate::AgentMetadata;
+
+/// Network limit on the size of a single contract data entry, in bytes
+///
+/// The `max_contract_data_entry_size_bytes` network setting (64 KiB).
+pub const MAX_ENTRY_SIZE: u32 = 65_536;
+
+/// Maximum number of agent ids kept in the enumeration index
+///
+/// The index is a single storage entry, so listing is capped; agents stored
+/// past the cap are kept but not listed.
+pub const MAX_INDEXED_AGENTS: u32 = 256;
+
+/// Storage keys for metadata entries
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub enum MetadataKey {
+    /// Validated metadata for an agent id
+    Agent(Bytes),
+    /// Agent id that registered a name
+    Name(Bytes),
+    /// Ids of the first `MAX_INDEXED_AGENTS` agents stored, in first-store order
+    Index,
+}
+
+/// Write metadata for `agent_id` to persistent storage
+///
+/// A new id is appended to the enumeration index while it holds fewer than
+/// `MAX_INDEXED_AGENTS` ids; past that the metadata is still written but the
+/// id is not listed.
+pub fn store(env: &Env, agent_id: &Bytes, metadata: &AgentMetadata) {
+    let key = MetadataKey::Agent(agent_id.clone());
+    if !env.storage().persistent().has(&key) {
+        let mut index = list(env);
+        if index.len() < MAX_INDEXED_AGENTS {
+            index.push_back(agent_id.clone());
+            env.storage().persistent().set(&MetadataKey::Index, &index);
+        }
+    }
+
+    env.storage().persistent().set(&key, metadata);
+}
+
+/// Delete the metadata for `agent_id` and drop it from the index
+///
+/// Returns whether anything was stored.
+pub fn remove(env: &Env, agent_id: &Bytes) -> bool {
+    let key = MetadataKey::Agent(agent_id.clone());
+    if !env.storage().persistent().has(&key) {
+        return false;
+    }
+    env.storage().persistent().remove(&key);
+
+    let mut index = list(env);
+    if let Some(position) = index.first_index_of(agent_id) {
+        index.remove(position);
+        env.storage().persistent().set(&MetadataKey::Index, &index);
+    }
+    true
+}
+
+/// Ids of the first `MAX_INDEXED_AGENTS` agents stored, in first-store order
+pub fn list(env: &Env) -> Vec<Bytes> {
+    env.storage().persistent().get(&MetadataKey::Index).unwrap_or_else(|| Vec::new(env))
+}
+
+/// Read metadata for `agent_id` from persistent storage
+pub fn load(env: &Env, agent_id: &Bytes) -> Option<AgentMetadata> {
+    env.storage().persistent().get(&MetadataKey::Agent(agent_id.clone()))
+}
+
+/// Agent id that registered `name`, if any
+pub fn name_owner(env: &Env, name: &Bytes) -> Option<Bytes> {
+    env.storage().persistent().get(&MetadataKey::Name(name.clone()))
+}
+
+/// Record `agent_id` as the owner of `name`
+pub fn set_name_owner(env: &Env, name: &Bytes, agent_id: &Bytes) {
+    env.storage().persistent().set(&MetadataKey::Name(name.clone()), agent_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::{ sample_input, TestContract };
+    use crate::MetadataValidator;
+
+    fn sample_metadata(env: &Env) -> AgentMetadata {
+        MetadataValidator::new().validate_input(env, sample_input(env)).unwrap()
+    }
+
+    #[test]
+    fn test_metadata_key_store_and_load() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+
+        env.as_contract(&contract_id, || {
+            let agent_id = Bytes::from_slice(&env, b"agent-1");
+            let metadata = sample_metadata(&env);
+
+            // Read and write through the public key type directly
+            let key = MetadataKey::Agent(agent_id.clone());
+            env.storage().persistent().set(&key, &metadata);
+            assert_eq!(load(&env, &agent_id), Some(metadata.clone()));
+
+            let other_id = Bytes::from_slice(&env, b"agent-2");
+            store(&env, &other_id, &metadata);
+            let loaded: Option<AgentMetadata> = env
+                .storage()
+                .persistent()
+                .get(&MetadataKey::Agent(other_id));
+            assert_eq!(loaded, Some(metadata));
+
+            assert_eq!(load(&env, &Bytes::from_slice(&env, b"missing")), None);
+        });
+    }
+
+    #[test]
+    fn test_index_lists_stored_agents() {
+        let env = Env::default();
+        let contract_id = env.register(TestContract, ());
+
+        env.as_contract(&contract_id, || {
+            let metadata = sample_metadata(&env);
+            let ids = [b"agent-1".as_slice(), b"agent-2", b"agent-3"];
+            for id in ids {
+                store(&env, &Bytes::from_slice(&env, id), &metadata);
+            }
+            // Overwriting does not duplicate the id
+            store(&env, &Bytes::from_slice(&env, b"agent-2"), &metadata);
+
+            let listed = list(&env);
+            assert_eq!(listed.len(), 3);
+            for id in ids {
+                assert!(listed.contains(&Bytes::from_slice(&env, id)));
+            }
+        });
+    }
+
+    #[test]
+    fn test_index_cap() {
+        let env = Env::default();
+        env.cost_estimate().budget().reset_unlimited();
+        let contract_id = env.register(TestContract, ());
+
+        env.as_contract(&contract_id, || {
+            let metadata = sample_metadata(&env);
+            for i in 0..MAX_INDEXED_AGENTS {
+                store(&env, &Bytes::from_array(&env, &i.to_be_bytes()), &metadata);
+            }
+
+            // Past the cap agents are still stored, just not listed
+            let overflow = Bytes::from_slice(&env, b"one-too-many");
+            store(&env, &overflow, &metadata);
+            assert_eq!(load(&env, &overflow), Some(metadata.clone()));
+            assert_eq!(list(&env).len(), MAX_INDEXED_AGENTS);
+            assert!(!list(&env).contains(&overflow));
+
+            let indexed = Bytes::from_array(&env, &0u32.to_be_bytes());
+            store(&env, &indexed, &metadata);
+            assert_eq!(list(&env).len(), MAX_INDEXED_AGENTS);
+        });
+    }
+}
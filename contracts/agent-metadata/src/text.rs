@@ -0,0 +1,343 @@
+//! Byte-level text helpers for metadata fields.
+//!
+//! Soroban `Bytes` carry no encoding guarantees, so these helpers work on raw
+//! ASCII byte classes rather than `str`.
+
+use soroban_sdk::{ Bytes, Env, Vec };
+
+/// Whether `b` is an ASCII whitespace byte (` `, `\t`, `\n`, `\r`)
+pub fn is_whitespace(b: u8) -> bool {
+    matches!(b, b' ' | b'\t' | b'\n' | b'\r')
+}
+
+/// Whether `text` consists solely of whitespace bytes
+///
+/// An empty input is reported as blank.
+pub fn is_blank(text: &Bytes) -> bool {
+    text.iter().all(is_whitespace)
+}
+
+/// Whether `text` is non-empty and consists solely of ASCII digits
+pub fn is_all_digits(text: &Bytes) -> bool {
+    !text.is_empty() && text.iter().all(|b| b.is_ascii_digit())
+}
+
+/// Number of words in `text`, splitting on runs of whitespace bytes
+pub fn word_count(text: &Bytes) -> u32 {
+    let mut count = 0;
+    let mut in_word = false;
+    for b in text.iter() {
+        if is_whitespace(b) {
+            in_word = false;
+        } else if !in_word {
+            in_word = true;
+            count += 1;
+        }
+    }
+    count
+}
+
+/// `text` with leading and trailing whitespace bytes removed
+pub fn trim(text: &Bytes) -> Bytes {
+    let mut start = 0;
+    let mut end = text.len();
+    while start < end && is_whitespace(text.get_unchecked(start)) {
+        start += 1;
+    }
+    while end > start && is_whitespace(text.get_unchecked(end - 1)) {
+        end -= 1;
+    }
+    text.slice(start..end)
+}
+
+/// Split `text` on every `separator` byte
+///
+/// Empty input yields no segments; otherwise empty segments (from leading,
+/// trailing or doubled separators) are kept so callers can reject them.
+pub fn split(env: &Env, text: &Bytes, separator: u8) -> Vec<Bytes> {
+    let mut segments = Vec::new(env);
+    if text.is_empty() {
+        return segments;
+    }
+
+    let mut start = 0;
+    for (i, b) in text.iter().enumerate() {
+        if b == separator {
+            segments.push_back(text.slice(start..i as u32));
+            start = i as u32 + 1;
+        }
+    }
+    segments.push_back(text.slice(start..));
+
+    segments
+}
+
+/// Longest input `edit_distance_at_most` compares, bounding its row buffers
+pub const MAX_EDIT_DISTANCE_INPUT: u32 = 256;
+
+/// Whether the Levenshtein distance between `a` and `b` is at most `max`
+///
+/// Bytes are compared as-is (ASCII). Only the diagonal band of width
+/// `2 * max + 1` is computed, and the scan stops as soon as a whole row
+/// exceeds `max`. Inputs longer than `MAX_EDIT_DISTANCE_INPUT` never match.
+pub fn edit_distance_at_most(a: &Bytes, b: &Bytes, max: u32) -> bool {
+    const UNREACHABLE: u32 = u32::MAX / 2;
+    const ROW: usize = MAX_EDIT_DISTANCE_INPUT as usize + 1;
+
+    if a.len().abs_diff(b.len()) > max {
+        return false;
+    }
+    if a.len() > MAX_EDIT_DISTANCE_INPUT || b.len() > MAX_EDIT_DISTANCE_INPUT {
+        return false;
+    }
+
+    let n = a.len() as usize;
+    let m = b.len() as usize;
+    let band = max as usize;
+
+    let mut prev = [UNREACHABLE; ROW];
+    let mut curr = [UNREACHABLE; ROW];
+    for (j, cell) in prev.iter_mut().enumerate().take(m.min(band) + 1) {
+        *cell = j as u32;
+    }
+
+    for i in 1..=n {
+        let lo = i.saturating_sub(band).max(1);
+        let hi = m.min(i.saturating_add(band));
+
+        curr[0] = if i <= band { i as u32 } else { UNREACHABLE };
+        curr[lo - 1] = if lo == 1 { curr[0] } else { UNREACHABLE };
+
+        let ai = a.get_unchecked((i - 1) as u32);
+        let mut row_min = curr[0];
+        for j in lo..=hi {
+            let cost = if ai == b.get_unchecked((j - 1) as u32) { 0 } else { 1 };
+            let value = (prev[j - 1] + cost).min(prev[j] + 1).min(curr[j - 1] + 1);
+            curr[j] = value;
+            row_min = row_min.min(value);
+        }
+        if hi < m {
+            curr[hi + 1] = UNREACHABLE;
+        }
+
+        if row_min > max {
+            return false;
+        }
+        core::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[m] <= max
+}
+
+/// Byte-wise equality ignoring ASCII case
+pub fn eq_ignore_ascii_case(a: &Bytes, b: &Bytes) -> bool {
+    a.len() == b.len() &&
+        a.iter().zip(b.iter()).all(|(x, y)| x.to_ascii_lowercase() == y.to_ascii_lowercase())
+}
+
+/// Copy of `text` with ASCII uppercase letters lowercased
+pub fn to_ascii_lowercase(env: &Env, text: &Bytes) -> Bytes {
+    let mut out = Bytes::new(env);
+    for b in text.iter() {
+        out.push_back(b.to_ascii_lowercase());
+    }
+    out
+}
+
+/// Whether `text` is free of ASCII control bytes (`0x00..=0x1F`, `0x7F`)
+///
+/// Bytes at or above `0x80` are allowed so UTF-8 text passes.
+pub fn is_printable(text: &Bytes) -> bool {
+    text.iter().all(|b| b >= 0x20 && b != 0x7F)
+}
+
+/// Whether `key` uses only the safe extra field key charset
+///
+/// ASCII alphanumerics plus `_`, `-`, `:` and `.`.
+pub fn is_safe_key(key: &Bytes) -> bool {
+    key.iter().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'-' | b':' | b'.'))
+}
+
+/// Maximum length of a DNS label in bytes
+pub const MAX_DNS_LABEL_LENGTH: u32 = 63;
+
+/// Whether `name` is a valid DNS label
+///
+/// 1 to 63 ASCII letters, digits or hyphens, not starting or ending with a
+/// hyphen.
+pub fn is_valid_dns_label(name: &Bytes) -> bool {
+    let len = name.len();
+    if len == 0 || len > MAX_DNS_LABEL_LENGTH {
+        return false;
+    }
+    if name.first() == Some(b'-') || name.last() == Some(b'-') {
+        return false;
+    }
+
+    name.iter().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+}
+
+/// Whether `text` is well-formed UTF-8
+///
+/// Rejects overlong encodings, surrogates and code points above U+10FFFF.
+pub fn is_valid_utf8(text: &Bytes) -> bool {
+    let len = text.len();
+    let mut i = 0;
+
+    while i < len {
+        let lead = text.get_unchecked(i);
+        let (width, min, max) = match lead {
+            0x00..=0x7F => {
+                i += 1;
+                continue;
+            }
+            0xC2..=0xDF => (2, 0x80, 0xBF),
+            0xE0 => (3, 0xA0, 0xBF),
+            0xED => (3, 0x80, 0x9F),
+            0xE1..=0xEC | 0xEE..=0xEF => (3, 0x80, 0xBF),
+            0xF0 => (4, 0x90, 0xBF),
+            0xF1..=0xF3 => (4, 0x80, 0xBF),
+            0xF4 => (4, 0x80, 0x8F),
+            _ => return false,
+        };
+
+        if i + width > len {
+            return false;
+        }
+
+        // The second byte carries the overlong/surrogate restrictions
+        let second = text.get_unchecked(i + 1);
+        if second < min || second > max {
+            return false;
+        }
+
+        for j in 2..width {
+            let b = text.get_unchecked(i + j);
+            if !(0x80..=0xBF).contains(&b) {
+                return false;
+            }
+        }
+
+        i += width;
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::Env;
+
+    #[test]
+    fn test_is_blank() {
+        let env = Env::default();
+
+        assert!(is_blank(&Bytes::from_slice(&env, b"")));
+        assert!(is_blank(&Bytes::from_slice(&env, b"   ")));
+        assert!(is_blank(&Bytes::from_slice(&env, b" \t\r\n")));
+        assert!(!is_blank(&Bytes::from_slice(&env, b"  a ")));
+    }
+
+    #[test]
+    fn test_word_count() {
+        let env = Env::default();
+
+        assert_eq!(word_count(&Bytes::from_slice(&env, b"")), 0);
+        assert_eq!(word_count(&Bytes::from_slice(&env, b" \t ")), 0);
+        assert_eq!(word_count(&Bytes::from_slice(&env, b"A test agent")), 3);
+        assert_eq!(word_count(&Bytes::from_slice(&env, b"  A  test\n\tagent  ")), 3);
+    }
+
+    #[test]
+    fn test_is_printable() {
+        let env = Env::default();
+
+        assert!(is_printable(&Bytes::from_slice(&env, b"Test Agent 1.0")));
+        assert!(is_printable(&Bytes::from_slice(&env, "caf\u{e9}".as_bytes())));
+        assert!(!is_printable(&Bytes::from_slice(&env, b"Test\x01Agent")));
+        assert!(!is_printable(&Bytes::from_slice(&env, b"Test\nAgent")));
+        assert!(!is_printable(&Bytes::from_slice(&env, b"Test\x7F")));
+    }
+
+    #[test]
+    fn test_is_valid_utf8() {
+        let env = Env::default();
+
+        assert!(is_valid_utf8(&Bytes::from_slice(&env, b"plain ascii")));
+        assert!(is_valid_utf8(&Bytes::from_slice(&env, "caf\u{e9} \u{20ac} \u{1f600}".as_bytes())));
+        assert!(!is_valid_utf8(&Bytes::from_slice(&env, &[0xC3])));
+        assert!(!is_valid_utf8(&Bytes::from_slice(&env, &[0xC0, 0xAF])));
+        assert!(!is_valid_utf8(&Bytes::from_slice(&env, &[0xED, 0xA0, 0x80])));
+        assert!(!is_valid_utf8(&Bytes::from_slice(&env, &[0xFF])));
+    }
+
+    #[test]
+    fn test_trim_and_case_folding() {
+        let env = Env::default();
+
+        assert_eq!(
+            trim(&Bytes::from_slice(&env, b" \t MyAgent \n")),
+            Bytes::from_slice(&env, b"MyAgent")
+        );
+        assert_eq!(trim(&Bytes::from_slice(&env, b"   ")), Bytes::new(&env));
+        assert!(eq_ignore_ascii_case(
+            &Bytes::from_slice(&env, b"MyAgent"),
+            &Bytes::from_slice(&env, b"myagent")
+        ));
+        assert!(!eq_ignore_ascii_case(
+            &Bytes::from_slice(&env, b"MyAgent"),
+            &Bytes::from_slice(&env, b"myagents")
+        ));
+    }
+
+    #[test]
+    fn test_is_safe_key() {
+        let env = Env::default();
+
+        assert!(is_safe_key(&Bytes::from_slice(&env, b"org:model.v2_build-1")));
+        assert!(!is_safe_key(&Bytes::from_slice(&env, b"bad key")));
+        assert!(!is_safe_key(&Bytes::from_slice(&env, b"key/path")));
+    }
+
+    #[test]
+    fn test_split() {
+        let env = Env::default();
+
+        let parts = split(&env, &Bytes::from_slice(&env, b"a,bc,,d"), b',');
+        assert_eq!(parts.len(), 4);
+        assert_eq!(parts.get(1), Some(Bytes::from_slice(&env, b"bc")));
+        assert_eq!(parts.get(2), Some(Bytes::new(&env)));
+
+        assert_eq!(split(&env, &Bytes::new(&env), b',').len(), 0);
+        assert_eq!(split(&env, &Bytes::from_slice(&env, b"solo"), b',').len(), 1);
+    }
+
+    #[test]
+    fn test_edit_distance_at_most() {
+        let env = Env::default();
+        let reference = Bytes::from_slice(&env, b"agent");
+        let within = |candidate: &[u8]| {
+            edit_distance_at_most(&reference, &Bytes::from_slice(&env, candidate), 1)
+        };
+
+        assert!(within(b"agent")); // distance 0
+        assert!(within(b"agant")); // substitution
+        assert!(within(b"agents")); // insertion
+        assert!(within(b"gent")); // deletion
+        assert!(!within(b"aggentt")); // distance 2
+        assert!(!within(b"aegnt")); // transposition counts as 2
+        assert!(!within(b"ag")); // length gap alone exceeds max
+    }
+
+    #[test]
+    fn test_is_valid_dns_label() {
+        let env = Env::default();
+
+        assert!(is_valid_dns_label(&Bytes::from_slice(&env, b"trading-agent-01")));
+        assert!(!is_valid_dns_label(&Bytes::from_slice(&env, b"trading_agent")));
+        assert!(!is_valid_dns_label(&Bytes::from_slice(&env, b"trading-")));
+        assert!(!is_valid_dns_label(&Bytes::from_slice(&env, b"-trading")));
+        assert!(!is_valid_dns_label(&Bytes::from_slice(&env, &[b'a'; 64])));
+    }
+}
@@ -0,0 +1,72 @@
+//! Internal arithmetic helpers shared by the size-limit checks.
+
+use soroban_sdk::Bytes;
+
+use crate::MetadataError;
+
+/// Sum `lengths`, failing with `InvalidLength` on `u32` overflow
+pub(crate) fn checked_total(lengths: &[u32]) -> Result<u32, MetadataError> {
+    lengths
+        .iter()
+        .try_fold(0u32, |total, &len| total.checked_add(len))
+        .ok_or(MetadataError::InvalidLength)
+}
+
+/// Parse a decimal `u64` extra field value
+///
+/// Rejects empty values, non-digit bytes and overflow with `InvalidStructure`.
+/// Unless `allow_leading_zeros` is set, a value with a redundant leading zero
+/// (`007`, but not `0`) is rejected too.
+pub(crate) fn parse_u64(value: &Bytes, allow_leading_zeros: bool) -> Result<u64, MetadataError> {
+    if value.is_empty() {
+        return Err(MetadataError::InvalidStructure);
+    }
+    if !allow_leading_zeros && value.len() > 1 && value.first() == Some(b'0') {
+        return Err(MetadataError::InvalidStructure);
+    }
+
+    let mut result: u64 = 0;
+    for b in value.iter() {
+        if !b.is_ascii_digit() {
+            return Err(MetadataError::InvalidStructure);
+        }
+        result = result
+            .checked_mul(10)
+            .and_then(|r| r.checked_add((b - b'0') as u64))
+            .ok_or(MetadataError::InvalidStructure)?;
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::Env;
+
+    #[test]
+    fn test_checked_total() {
+        assert_eq!(checked_total(&[]), Ok(0));
+        assert_eq!(checked_total(&[1, 2, 3]), Ok(6));
+        assert_eq!(checked_total(&[u32::MAX - 1, 1]), Ok(u32::MAX));
+        assert_eq!(checked_total(&[u32::MAX - 1, 1, 1]), Err(MetadataError::InvalidLength));
+    }
+
+    #[test]
+    fn test_parse_u64() {
+        let env = Env::default();
+        let parse = |value: &[u8], allow_leading_zeros| {
+            parse_u64(&Bytes::from_slice(&env, value), allow_leading_zeros)
+        };
+
+        assert_eq!(parse(b"1700000000", false), Ok(1700000000));
+        assert_eq!(parse(b"0", false), Ok(0));
+        assert_eq!(parse(b"18446744073709551615", false), Ok(u64::MAX));
+        assert_eq!(parse(b"18446744073709551616", false), Err(MetadataError::InvalidStructure));
+        assert_eq!(parse(b"12a", false), Err(MetadataError::InvalidStructure));
+        assert_eq!(parse(b"", false), Err(MetadataError::InvalidStructure));
+
+        assert_eq!(parse(b"007", false), Err(MetadataError::InvalidStructure));
+        assert_eq!(parse(b"007", true), Ok(7));
+    }
+}
@@ -0,0 +1,151 @@
+//! Version scheme helpers for the `version` field.
+
+use soroban_sdk::Bytes;
+
+/// Development placeholder version
+pub const ZERO: &[u8] = b"0.0.0";
+
+/// Whether `version` is exactly the placeholder `0.0.0`
+pub fn is_zero(version: &Bytes) -> bool {
+    version.len() == ZERO.len() as u32 && version.iter().eq(ZERO.iter().copied())
+}
+
+/// Whether `version` carries a semver pre-release suffix (`1.0.0-beta`)
+///
+/// A pre-release is marked by a `-` before any `+` build metadata, so
+/// `1.0.0+build-7` is not a pre-release.
+pub fn is_prerelease(version: &Bytes) -> bool {
+    for b in version.iter() {
+        match b {
+            b'-' => {
+                return true;
+            }
+            b'+' => {
+                return false;
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Whether `version` is `MAJOR.MINOR.PATCH` with an optional suffix
+///
+/// Each core part is a decimal number without leading zeros. A suffix
+/// starts with `-` or `+` and is checked loosely: at least one more byte,
+/// all ASCII alphanumerics, `.`, `-` or `+`.
+pub fn is_valid_semver(version: &Bytes) -> bool {
+    let core_end = version
+        .iter()
+        .position(|b| b == b'-' || b == b'+')
+        .map_or(version.len(), |i| i as u32);
+
+    let mut dots = 0;
+    let mut part_len = 0;
+    let mut leading_zero = false;
+    for b in version.slice(0..core_end).iter() {
+        if b == b'.' {
+            if part_len == 0 {
+                return false;
+            }
+            dots += 1;
+            part_len = 0;
+        } else if b.is_ascii_digit() {
+            if part_len == 1 && leading_zero {
+                return false;
+            }
+            leading_zero = part_len == 0 && b == b'0';
+            part_len += 1;
+        } else {
+            return false;
+        }
+    }
+    if dots != 2 || part_len == 0 {
+        return false;
+    }
+
+    let suffix = version.slice(core_end..);
+    suffix.len() != 1 &&
+        suffix
+            .iter()
+            .skip(1)
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'-' | b'+'))
+}
+
+/// Whether `version` is a `YYYY.MM.DD` calendar version
+///
+/// A four-digit year, then a month of 1 to 12 and a day of 1 to 31, each one
+/// or two digits. Days are not checked against the month.
+pub fn is_valid_calver(version: &Bytes) -> bool {
+    let mut values = [0u32; 3];
+    let mut digits = [0u32; 3];
+    let mut part = 0;
+    for b in version.iter() {
+        if b == b'.' {
+            part += 1;
+            if part > 2 {
+                return false;
+            }
+        } else if b.is_ascii_digit() && digits[part] < 4 {
+            values[part] = values[part] * 10 + (b - b'0') as u32;
+            digits[part] += 1;
+        } else {
+            return false;
+        }
+    }
+
+    part == 2 &&
+        digits[0] == 4 &&
+        (1..=2).contains(&digits[1]) &&
+        (1..=2).contains(&digits[2]) &&
+        (1..=12).contains(&values[1]) &&
+        (1..=31).contains(&values[2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use soroban_sdk::Env;
+
+    #[test]
+    fn test_is_prerelease() {
+        let env = Env::default();
+
+        assert!(is_prerelease(&Bytes::from_slice(&env, b"1.0.0-beta")));
+        assert!(is_prerelease(&Bytes::from_slice(&env, b"1.0.0-rc.1+build")));
+        assert!(!is_prerelease(&Bytes::from_slice(&env, b"1.0.0")));
+        assert!(!is_prerelease(&Bytes::from_slice(&env, b"1.0.0+build-7")));
+    }
+
+    #[test]
+    fn test_is_valid_semver() {
+        let env = Env::default();
+
+        assert!(is_valid_semver(&Bytes::from_slice(&env, b"1.0.0")));
+        assert!(is_valid_semver(&Bytes::from_slice(&env, b"10.2.3-rc.1+build")));
+        assert!(!is_valid_semver(&Bytes::from_slice(&env, b"1.0")));
+        assert!(!is_valid_semver(&Bytes::from_slice(&env, b"01.0.0")));
+        assert!(!is_valid_semver(&Bytes::from_slice(&env, b"1.0.0-")));
+    }
+
+    #[test]
+    fn test_is_valid_calver() {
+        let env = Env::default();
+
+        assert!(is_valid_calver(&Bytes::from_slice(&env, b"2024.06.15")));
+        assert!(is_valid_calver(&Bytes::from_slice(&env, b"2024.6.1")));
+        assert!(!is_valid_calver(&Bytes::from_slice(&env, b"1.0.0")));
+        assert!(!is_valid_calver(&Bytes::from_slice(&env, b"2024.13.01")));
+        assert!(!is_valid_calver(&Bytes::from_slice(&env, b"2024.06.32")));
+        assert!(!is_valid_calver(&Bytes::from_slice(&env, b"2024.06.15.1")));
+    }
+
+    #[test]
+    fn test_is_zero() {
+        let env = Env::default();
+
+        assert!(is_zero(&Bytes::from_slice(&env, b"0.0.0")));
+        assert!(!is_zero(&Bytes::from_slice(&env, b"0.1.0")));
+        assert!(!is_zero(&Bytes::from_slice(&env, b"0.0.0-dev")));
+    }
+}
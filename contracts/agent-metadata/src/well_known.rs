@@ -0,0 +1,191 @@
+//! Well-known extra field keys and their value rules.
+
+use soroban_sdk::{ Bytes, Env };
+
+use crate::{ util, MetadataError };
+
+/// Key of the `json_cid` scalar field in flattened output
+pub const JSON_CID: &[u8] = b"json_cid";
+
+/// Key of the `model_hash` scalar field in flattened output
+pub const MODEL_HASH: &[u8] = b"model_hash";
+
+/// Key of the `name` scalar field in flattened output
+pub const NAME: &[u8] = b"name";
+
+/// Key of the `description` scalar field in flattened output
+pub const DESCRIPTION: &[u8] = b"description";
+
+/// Key of the `version` scalar field in flattened output
+pub const VERSION: &[u8] = b"version";
+
+/// Keys of every scalar field, in declaration order
+pub const SCALAR_KEYS: [&[u8]; 5] = [JSON_CID, MODEL_HASH, NAME, DESCRIPTION, VERSION];
+
+/// Whether `key` names a scalar field
+pub fn is_scalar_key(key: &Bytes) -> bool {
+    SCALAR_KEYS.iter().any(|scalar| is(key, scalar))
+}
+
+fn is(bytes: &Bytes, expected: &[u8]) -> bool {
+    bytes.len() == expected.len() as u32 && bytes.iter().eq(expected.iter().copied())
+}
+
+/// Unix timestamp (decimal seconds) at which the agent was created
+pub const CREATED_AT: &[u8] = b"created_at";
+
+/// Model serialization format (e.g. `onnx`, `gguf`, `safetensors`)
+pub const CONTENT_TYPE: &[u8] = b"content_type";
+
+/// Semantic agent category (e.g. `trading`, `analysis`, `execution`)
+pub const KIND: &[u8] = b"kind";
+
+/// Comma-separated list of additional asset CIDs (weights, config)
+pub const ASSETS: &[u8] = b"assets";
+
+/// CID of a detached signature over the model
+pub const SIG_CID: &[u8] = b"sig_cid";
+
+/// Comma-separated list of free-form tags
+pub const TAGS: &[u8] = b"tags";
+
+/// Model size in bytes as a positive decimal integer
+pub const MODEL_SIZE_BYTES: &[u8] = b"model_size_bytes";
+
+/// Deprecation marker holding `true` or `false`
+pub const DEPRECATED: &[u8] = b"deprecated";
+
+/// Platform-assigned rank as a decimal integer
+pub const RANK: &[u8] = b"rank";
+
+/// Comma-separated list of maintainer ed25519 public keys in hex
+pub const MAINTAINERS: &[u8] = b"maintainers";
+
+/// Length of a raw ed25519 public key in bytes
+pub const MAINTAINER_KEY_LENGTH: u32 = 32;
+
+/// Project homepage as an `http://` or `https://` URL
+pub const HOMEPAGE: &[u8] = b"homepage";
+
+/// License identifier in SPDX style (e.g. `MIT`, `Apache-2.0`)
+pub const LICENSE: &[u8] = b"license";
+
+/// Common SPDX license identifiers, matched exactly
+pub const KNOWN_SPDX: [&[u8]; 12] = [
+    b"MIT",
+    b"Apache-2.0",
+    b"GPL-2.0-only",
+    b"GPL-2.0-or-later",
+    b"GPL-3.0-only",
+    b"GPL-3.0-or-later",
+    b"LGPL-3.0-only",
+    b"BSD-2-Clause",
+    b"BSD-3-Clause",
+    b"MPL-2.0",
+    b"ISC",
+    b"Unlicense",
+];
+
+/// Longest license identifier accepted by the format fallback
+pub const MAX_SPDX_LENGTH: u32 = 64;
+
+/// Whether `id` looks like an SPDX license identifier
+///
+/// Identifiers in `KNOWN_SPDX` are accepted outright; anything else must be
+/// 1 to 64 ASCII letters, digits, `-` or `.`, starting with a letter or
+/// digit, optionally followed by the `+` "or later" suffix (`GPL-2.0+`).
+/// Compound expressions (`MIT OR Apache-2.0`) are not supported.
+pub fn is_valid_spdx(id: &Bytes) -> bool {
+    if KNOWN_SPDX.iter().any(|known| is(id, known)) {
+        return true;
+    }
+
+    let body = match id.last() {
+        Some(b'+') => id.slice(..id.len() - 1),
+        _ => id.clone(),
+    };
+    id.len() <= MAX_SPDX_LENGTH &&
+        body.first().is_some_and(|b| b.is_ascii_alphanumeric()) &&
+        body.iter().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.'))
+}
+
+/// Check `value` against the expected shape for well-known `key`
+///
+/// `homepage` must be an `http(s)://` URL without spaces or control bytes,
+/// `license` an identifier accepted by `is_valid_spdx`, the numeric
+/// keys (`created_at`, `model_size_bytes`, `rank`) decimal integers and
+/// `deprecated` `true` or `false`. Unknown keys accept any value. Shape
+/// mismatches fail with `InvalidStructure`.
+pub fn validate_value(_env: &Env, key: &Bytes, value: &Bytes) -> Result<(), MetadataError> {
+    let valid = if is(key, HOMEPAGE) {
+        is_url_like(value)
+    } else if is(key, LICENSE) {
+        is_valid_spdx(value)
+    } else if is(key, CREATED_AT) || is(key, MODEL_SIZE_BYTES) || is(key, RANK) {
+        util::parse_u64(value, true).is_ok()
+    } else if is(key, DEPRECATED) {
+        is(value, b"true") || is(value, b"false")
+    } else {
+        true
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(MetadataError::InvalidStructure)
+    }
+}
+
+fn is_url_like(value: &Bytes) -> bool {
+    let scheme_len = if value.len() > 8 && is(&value.slice(0..8), b"https://") {
+        8
+    } else if value.len() > 7 && is(&value.slice(0..7), b"http://") {
+        7
+    } else {
+        return false;
+    };
+    value.slice(scheme_len..).iter().all(|b| b.is_ascii_graphic())
+}
+
+/// Prefix marking extra field keys reserved for platform use
+pub const RESERVED_PREFIX: &[u8] = b"_";
+
+/// Whether `key` falls in the reserved namespace
+pub fn is_reserved_key(key: &Bytes) -> bool {
+    key.get(0) == Some(RESERVED_PREFIX[0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_spdx() {
+        let env = Env::default();
+
+        assert!(is_valid_spdx(&Bytes::from_slice(&env, b"MIT")));
+        assert!(is_valid_spdx(&Bytes::from_slice(&env, b"LicenseRef-Internal-1.0")));
+        assert!(!is_valid_spdx(&Bytes::from_slice(&env, b"not a license!")));
+        assert!(!is_valid_spdx(&Bytes::from_slice(&env, b"-MIT")));
+        assert!(is_valid_spdx(&Bytes::from_slice(&env, b"GPL-2.0+")));
+        assert!(!is_valid_spdx(&Bytes::from_slice(&env, b"GPL+2.0")));
+        assert!(!is_valid_spdx(&Bytes::from_slice(&env, b"+")));
+        assert!(!is_valid_spdx(&Bytes::new(&env)));
+    }
+
+    #[test]
+    fn test_validate_value() {
+        let env = Env::default();
+        let check = |key: &[u8], value: &[u8]| {
+            validate_value(&env, &Bytes::from_slice(&env, key), &Bytes::from_slice(&env, value))
+        };
+
+        assert_eq!(check(HOMEPAGE, b"https://example.com/agent"), Ok(()));
+        assert_eq!(check(HOMEPAGE, b"example.com"), Err(MetadataError::InvalidStructure));
+        assert_eq!(check(LICENSE, b"not a license!"), Err(MetadataError::InvalidStructure));
+        assert_eq!(check(LICENSE, b"-MIT"), Err(MetadataError::InvalidStructure));
+        assert_eq!(check(LICENSE, b"GPL-2.0+"), Ok(()));
+        assert_eq!(check(CREATED_AT, b"soon"), Err(MetadataError::InvalidStructure));
+        assert_eq!(check(b"nickname", b"anything at all!"), Ok(()));
+    }
+}
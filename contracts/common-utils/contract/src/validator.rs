@@ -84,6 +84,18 @@ impl ValidatorConfig {
     }
 }
 
+/// Shortest CID accepted by `CIDValidator::new`
+pub const CID_MIN_LENGTH: usize = 10;
+
+/// Longest CID accepted by `CIDValidator::new`
+pub const CID_MAX_LENGTH: usize = 100;
+
+/// Shortest hash accepted by `HashValidator::new`
+pub const HASH_MIN_LENGTH: usize = 32;
+
+/// Longest hash accepted by `HashValidator::new`
+pub const HASH_MAX_LENGTH: usize = 128;
+
 /// CID validator with configurable bounds
 #[derive(Clone, Debug)]
 pub struct CIDValidator {
@@ -94,7 +106,7 @@ impl CIDValidator {
     pub fn new() -> Self {
         Self {
             config: ValidatorConfig::new()
-                .with_length_bounds(10, 100)
+                .with_length_bounds(CID_MIN_LENGTH, CID_MAX_LENGTH)
                 .strict(false)
         }
     }
@@ -214,7 +226,7 @@ impl HashValidator {
     pub fn new() -> Self {
         Self {
             config: ValidatorConfig::new()
-                .with_length_bounds(32, 128)
+                .with_length_bounds(HASH_MIN_LENGTH, HASH_MAX_LENGTH)
                 .strict(false),
             algorithm: HashAlgorithm::SHA256,
             verify_checksum: false,